@@ -11,31 +11,94 @@ from polars.plugins import register_plugin_function
 from . import polars_network as _native
 
 if TYPE_CHECKING:  # pragma: no cover
-    from typing import Sequence
+    from typing import Any, Literal, Sequence
+
+    AddressOutput = Literal["int64", "string", "struct"]
+    IntegerOutput = Literal["int64", "struct"]
+    AddressStyle = Literal["compressed", "exploded", "reverse_pointer"]
+    MixedFamily = Literal["null", "split", "mapped"]
+    CountOutput = Literal["uint64", "float64", "string"]
+    Notation = Literal["netmask", "wildcard", "glob", "range"]
+    ParseMode = Literal["strict", "lenient"] | Notation | Sequence[Notation]
+    OnError = Literal["null", "raise", "skip"]
 
 
 PLUGIN_PATH = Path(_native.__file__).parent
 __version__ = _native.__version__
 
+_NOTATIONS = ("netmask", "wildcard", "glob", "range")
+
 
-def _plugin_expr(function_name: str, args: Sequence[pl.Expr]) -> pl.Expr:
+def _plugin_expr(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr:
     return register_plugin_function(
         plugin_path=PLUGIN_PATH,
         function_name=function_name,
         args=args,
+        kwargs=kwargs,
         is_elementwise=True,
     )
 
 
-def _plugin_agg(function_name: str, args: Sequence[pl.Expr]) -> pl.Expr:
+def _plugin_window(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr:
     return register_plugin_function(
         plugin_path=PLUGIN_PATH,
         function_name=function_name,
         args=args,
+        kwargs=kwargs,
         is_elementwise=False,
+        returns_scalar=False,
     )
 
 
+def _plugin_agg(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr:
+    return register_plugin_function(
+        plugin_path=PLUGIN_PATH,
+        function_name=function_name,
+        args=args,
+        kwargs=kwargs,
+        is_elementwise=False,
+    )
+
+
+def _plugin_scalar_agg(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr:
+    return register_plugin_function(
+        plugin_path=PLUGIN_PATH,
+        function_name=function_name,
+        args=args,
+        kwargs=kwargs,
+        is_elementwise=False,
+        returns_scalar=True,
+    )
+
+
+def _cidr_kwargs(parse: ParseMode, on_error: OnError, **kwargs: Any) -> dict[str, Any]:
+    if parse == "strict":
+        notations = []
+    elif parse == "lenient":
+        notations = list(_NOTATIONS)
+    elif isinstance(parse, str):
+        notations = [parse]
+    else:
+        notations = list(parse)
+
+    unknown = [notation for notation in notations if notation not in _NOTATIONS]
+    if unknown:
+        msg = (
+            f"invalid parse mode {parse!r}: expected 'strict', 'lenient' or notations among "
+            f"{', '.join(map(repr, _NOTATIONS))} (got {', '.join(map(repr, unknown))})"
+        )
+        raise ValueError(msg)
+    return {**kwargs, "notations": notations, "on_error": on_error}
+
+
 def _to_expr(value: IntoExpr) -> pl.Expr:
     if isinstance(value, pl.Expr):
         return value
@@ -44,48 +107,579 @@ def _to_expr(value: IntoExpr) -> pl.Expr:
 
 @register_expr_namespace("cidr")
 class CidrNamespace:
+    """Expressions over CIDR networks.
+
+    Values must use ``addr/prefix`` notation unless ``parse`` allows others: ``"lenient"`` also
+    accepts netmasks (``10.0.0.0 255.255.255.0``), Cisco wildcard masks (``10.0.0.0 0.0.0.255``),
+    globs (``10.0.0.*``) and ranges forming exactly one network (``10.0.0.0-10.0.0.255``), while
+    one of ``"netmask"``, ``"wildcard"``, ``"glob"`` and ``"range"`` or a sequence of them enables
+    only those, tried in order. Any notation also makes parsing ignore leading and trailing
+    whitespace.
+
+    Masks valid in both readings never widen a host entry: ``10.0.0.5 0.0.0.0`` and
+    ``10.0.0.5 255.255.255.255`` both parse as ``10.0.0.5/32``. Ranges spanning several networks,
+    such as ``10.0.0.1-10.0.0.9``, stay invalid and their error points to :func:`from_range`,
+    which lists their CIDRs.
+
+    Values that still fail to parse are treated as nulls unless ``on_error`` says otherwise:
+    ``"raise"`` fails with the offending value and row, while ``"skip"`` also treats them as nulls
+    but drops them from list arguments instead of nulling the whole list.
+    """
+
     def __init__(self, expr: pl.Expr):
         self._expr = expr
 
-    def contains(self, other: IntoExpr) -> pl.Expr:
+    def contains(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
         """Return a boolean expression indicating whether ``self`` contains ``other``."""
-        return _plugin_expr("cidr_contains", (self._expr, _to_expr(other)))
+        return _plugin_expr(
+            "cidr_contains", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
 
-    def subnet_of(self, other: IntoExpr) -> pl.Expr:
+    def subnet_of(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
         """Return a boolean expression indicating whether ``self`` is a subnet of ``other``."""
-        return _plugin_expr("cidr_subnet_of", (self._expr, _to_expr(other)))
+        return _plugin_expr(
+            "cidr_subnet_of", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
 
-    def contains_any(self, other: IntoExpr) -> pl.Expr:
+    def contains_any(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
         """Return a boolean expression indicating whether ``self`` contains any CIDR in ``other``."""
-        return _plugin_expr("cidr_contains_any", (self._expr, _to_expr(other)))
+        return _plugin_expr(
+            "cidr_contains_any", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
 
-    def subnet_of_any(self, other: IntoExpr) -> pl.Expr:
+    def subnet_of_any(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
         """Return a boolean expression indicating whether ``self`` is a subnet of any CIDR in ``other``."""
-        return _plugin_expr("cidr_subnet_of_any", (self._expr, _to_expr(other)))
+        return _plugin_expr(
+            "cidr_subnet_of_any", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
+
+    def overlaps(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return a boolean expression indicating whether ``self`` shares any address with ``other``.
+
+        ``other`` is a CIDR literal, a CIDR column, or a list in which case any overlapping entry matches.
+        """
+        return _plugin_expr(
+            "cidr_overlaps", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
+
+    def intersection(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return an expression with the prefix shared by ``self`` and ``other``, or null when they are disjoint."""
+        return _plugin_expr(
+            "cidr_intersection", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
+
+    def exclude(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return the minimal list of CIDRs covering ``self`` minus the CIDR or CIDR list ``other``.
+
+        Like :meth:`ipaddress.IPv4Network.address_exclude`, but ``other`` may hold several networks,
+        including ones only partially overlapping ``self``.
+        """
+        return _plugin_expr(
+            "cidr_exclude", (self._expr, _to_expr(other)), _cidr_kwargs(parse, on_error)
+        )
+
+    def lpm_lookup(
+        self,
+        prefixes: IntoExpr | pl.Series,
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return the index of the longest prefix in ``prefixes`` containing each CIDR in ``self``.
+
+        ``prefixes`` is a list literal, a :class:`polars.Series` or a column; rows without a
+        matching prefix yield null. A column is indexed as a whole, so the lookup runs over the
+        full frame rather than per batch, and per partition within ``.over(...)``.
+        """
+        if isinstance(prefixes, pl.Series):
+            prefixes = pl.lit(prefixes.implode())
+        elif isinstance(prefixes, pl.Expr):
+            return _plugin_window(
+                "cidr_lpm_lookup", (self._expr, prefixes), _cidr_kwargs(parse, on_error)
+            )
+        return _plugin_expr(
+            "cidr_lpm_lookup", (self._expr, _to_expr(prefixes)), _cidr_kwargs(parse, on_error)
+        )
+
+    def is_root(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return a boolean expression indicating whether ``self`` is not contained in any other CIDR within the column.
+
+        The result depends on the whole column, so within ``.over(...)`` or ``group_by().agg(...)``
+        roots are computed per partition.
+        """
+        return _plugin_window("cidr_is_root", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def parent(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the most specific other CIDR of the column strictly containing each CIDR.
+
+        Rows without a containing prefix yield null; within ``.over(...)`` parents are searched per partition.
+        Rows sharing a network with different host bits, such as ``10.0.0.1/24`` and ``10.0.0.2/24``,
+        are treated as duplicates so the parent is always another row's value; :meth:`is_root`
+        instead counts them as containing each other and flags neither as a root.
+        """
+        return _plugin_window("cidr_parent", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def depth(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the number of distinct prefixes of the column strictly containing each CIDR.
+
+        Rows sharing a network with different host bits are treated as duplicates, as in :meth:`parent`,
+        so ``depth()`` is 0 exactly where :meth:`parent` is null.
+        """
+        return _plugin_window("cidr_depth", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def overlapping_with(
+        self, values: bool = False, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return, for each CIDR in ``self``, the other rows of the column whose networks overlap it.
+
+        Row indices are returned unless ``values`` is ``True``, in which case the overlapping CIDRs
+        are. Within ``.over(...)`` or ``group_by().agg(...)`` overlaps are searched per partition.
+        """
+        return _plugin_window(
+            "cidr_overlapping_with", (self._expr,), _cidr_kwargs(parse, on_error, values=values)
+        )
+
+    def network_address(
+        self,
+        output: AddressOutput = "int64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return an expression with the network address of each CIDR in ``self``.
+
+        ``output`` selects the representation: ``"int64"`` (null for IPv6), ``"string"``, or
+        ``"struct"`` holding the ``addr_hi``/``addr_lo`` halves of the 128-bit address.
+        """
+        return _plugin_expr(
+            "cidr_network_address", (self._expr,), _cidr_kwargs(parse, on_error, output=output)
+        )
+
+    def broadcast_address(
+        self,
+        output: AddressOutput = "int64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return an expression with the broadcast address of each CIDR in ``self``.
+
+        ``output`` accepts the same values as :meth:`network_address`.
+        """
+        return _plugin_expr(
+            "cidr_broadcast_address", (self._expr,), _cidr_kwargs(parse, on_error, output=output)
+        )
+
+    def network(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the network address of each CIDR in ``self`` as a string."""
+        return self.network_address(output="string", parse=parse, on_error=on_error)
+
+    def broadcast(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the broadcast (last) address of each CIDR in ``self`` as a string."""
+        return self.broadcast_address(output="string", parse=parse, on_error=on_error)
+
+    def first_host(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the first usable host address of each CIDR in ``self``.
+
+        IPv4 /31 and /32 and IPv6 /127 and /128 networks have no reserved addresses.
+        """
+        return _plugin_expr("cidr_first_host", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def last_host(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the last usable host address of each CIDR in ``self``.
+
+        IPv4 /31 and /32 and IPv6 networks have no reserved broadcast address.
+        """
+        return _plugin_expr("cidr_last_host", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def to_range(
+        self,
+        output: AddressOutput = "string",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return an expression with the first and last address of each CIDR in ``self`` as a ``{start, end}`` struct.
+
+        ``output`` applies to both bounds and accepts the same values as :meth:`network_address`.
+        """
+        return _plugin_expr(
+            "cidr_to_range", (self._expr,), _cidr_kwargs(parse, on_error, output=output)
+        )
+
+    def nth_host(
+        self, n: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return an expression with the address ``n`` positions into each CIDR in ``self``.
+
+        Indexing counts from the network address, and negative ``n`` from one past the last
+        address, but only usable hosts are returned: ``1`` is the first host and ``-2`` the last
+        IPv4 host (``-1`` for IPv6, IPv4 ``/31`` and ``/32``). Other positions, such as the network
+        and broadcast addresses, yield null.
+        """
+        return _plugin_expr(
+            "cidr_nth_host", (self._expr, _to_expr(n)), _cidr_kwargs(parse, on_error)
+        )
+
+    def next(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the network of the same size following each CIDR in ``self``."""
+        return _plugin_expr("cidr_next", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def previous(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the network of the same size preceding each CIDR in ``self``."""
+        return _plugin_expr("cidr_previous", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def num_addresses(
+        self,
+        output: CountOutput = "uint64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return an expression with the number of addresses in each CIDR in ``self``.
+
+        ``output`` is ``"uint64"`` (null for IPv6 networks of 2**64 addresses or more), ``"float64"``,
+        or ``"string"`` holding the exact decimal count.
+        """
+        return _plugin_expr(
+            "cidr_num_addresses", (self._expr,), _cidr_kwargs(parse, on_error, output=output)
+        )
 
-    def is_root(self) -> pl.Expr:
-        """Return a boolean expression indicating whether ``self`` is not contained in any other CIDR within the column."""
-        return _plugin_expr("cidr_is_root", (self._expr,))
+    def num_hosts(
+        self,
+        output: CountOutput = "uint64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return an expression with the number of usable hosts in each CIDR in ``self``, as listed by :meth:`hosts`.
 
-    def network_address(self) -> pl.Expr:
-        """Return an expression with the network address of each CIDR in ``self``."""
-        return _plugin_expr("cidr_network_address", (self._expr,))
+        ``output`` accepts the same values as :meth:`num_addresses`.
+        """
+        return _plugin_expr(
+            "cidr_num_hosts", (self._expr,), _cidr_kwargs(parse, on_error, output=output)
+        )
 
-    def broadcast_address(self) -> pl.Expr:
-        """Return an expression with the broadcast address of each CIDR in ``self``."""
-        return _plugin_expr("cidr_broadcast_address", (self._expr,))
+    def hosts(
+        self, limit: int = 65_536, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return an expression listing the usable host addresses of each CIDR in ``self``.
 
-    def netmask(self, binary: IntoExpr = False) -> pl.Expr:
+        Hosts span :meth:`first_host` to :meth:`last_host`; a network holding more than ``limit``
+        hosts raises a ``ComputeError`` rather than allocating the whole range.
+        """
+        return _plugin_expr("cidr_hosts", (self._expr,), _cidr_kwargs(parse, on_error, limit=limit))
+
+    def netmask(
+        self, binary: IntoExpr = False, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
         """Return an expression with the CIDR prefix length or IPv4 mask when ``binary`` is ``True``."""
-        return _plugin_expr("cidr_netmask", (self._expr, _to_expr(binary)))
+        return _plugin_expr(
+            "cidr_netmask", (self._expr, _to_expr(binary)), _cidr_kwargs(parse, on_error)
+        )
+
+    def subnets(
+        self,
+        new_prefix: IntoExpr,
+        max_count: int = 65_536,
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return an expression listing the ``/new_prefix`` subnets of each CIDR in ``self``.
+
+        Rows whose ``new_prefix`` is shorter than their own prefix yield null; a row expanding to
+        more than ``max_count`` subnets raises a ``ComputeError``.
+        """
+        return _plugin_expr(
+            "cidr_subnets",
+            (self._expr, _to_expr(new_prefix)),
+            _cidr_kwargs(parse, on_error, max_count=max_count),
+        )
+
+    def subnets_count(
+        self, new_prefix: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return an expression with the number of ``/new_prefix`` subnets of each CIDR in ``self``.
 
-    def version(self) -> pl.Expr:
+        Counts of 2**64 or more, only reachable with IPv6, yield null.
+        """
+        return _plugin_expr(
+            "cidr_subnets_count", (self._expr, _to_expr(new_prefix)), _cidr_kwargs(parse, on_error)
+        )
+
+    def version(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
         """Return an expression indicating whether each CIDR is IPv4 or IPv6."""
-        return _plugin_expr("cidr_version", (self._expr,))
+        return _plugin_expr("cidr_version", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def is_valid(self, allow_host_bits: bool = False, parse: ParseMode = "strict") -> pl.Expr:
+        """Return a boolean expression indicating whether each value in ``self`` is a valid CIDR.
+
+        Networks with host bits set, such as ``10.1.2.3/8``, are invalid unless ``allow_host_bits``
+        is ``True``.
+        """
+        return _plugin_expr(
+            "cidr_is_valid",
+            (self._expr,),
+            _cidr_kwargs(parse, "null", allow_host_bits=allow_host_bits),
+        )
+
+    def parse_error(self, allow_host_bits: bool = False, parse: ParseMode = "strict") -> pl.Expr:
+        """Return an expression with the reason each value in ``self`` is not a valid CIDR.
+
+        Reasons include empty values, surrounding whitespace, bad octets, out-of-range prefixes,
+        host bits set and mixed address families; valid and null values yield null.
+        """
+        return _plugin_expr(
+            "cidr_parse_error",
+            (self._expr,),
+            _cidr_kwargs(parse, "null", allow_host_bits=allow_host_bits),
+        )
+
+    def has_host_bits(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return a boolean expression indicating whether each CIDR in ``self`` has host bits set, like ``10.1.2.3/8``."""
+        return _plugin_expr("cidr_has_host_bits", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def normalize(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression with the canonical string of each CIDR in ``self``.
+
+        Host bits are cleared and IPv6 addresses compressed in lowercase, so equal networks compare
+        equal as strings.
+        """
+        return _plugin_expr("cidr_normalize", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def supernet(
+        self,
+        mixed_family: MixedFamily = "null",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return a group-by aggregation producing the minimal supernet per group.
+
+        ``mixed_family`` controls groups holding both IPv4 and IPv6 networks: ``"null"`` yields null,
+        ``"split"`` returns a ``{v4, v6}`` struct with one supernet per family, and ``"mapped"``
+        maps IPv4 networks into ``::ffff:0:0/96`` before computing a single IPv6 supernet.
+        """
+        return _plugin_agg(
+            "cidr_supernet", (self._expr,), _cidr_kwargs(parse, on_error, mixed_family=mixed_family)
+        )
+
+    def collapse(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return a group-by aggregation merging each group's CIDRs into the minimal covering list.
+
+        Adjacent and overlapping prefixes are merged exactly, like :func:`ipaddress.collapse_addresses`;
+        IPv4 networks are listed before IPv6 ones.
+        """
+        return _plugin_scalar_agg("cidr_collapse", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def num_unique_addresses(
+        self,
+        output: CountOutput = "uint64",
+        mixed_family: MixedFamily = "null",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr:
+        """Return a group-by aggregation counting the distinct addresses covered by each group's CIDRs.
+
+        Overlapping prefixes are counted once; ``output`` accepts the same values as :meth:`num_addresses`.
+        ``mixed_family`` treats dual-stack groups as in :meth:`supernet`: ``"null"`` yields null,
+        ``"split"`` returns a ``{v4, v6}`` struct with one count per family, null for a family the
+        group lacks, and ``"mapped"`` maps IPv4 networks into ``::ffff:0:0/96`` before counting, so
+        addresses also covered by IPv4-mapped IPv6 networks are counted once.
+        """
+        return _plugin_scalar_agg(
+            "cidr_num_unique_addresses",
+            (self._expr,),
+            _cidr_kwargs(parse, on_error, output=output, mixed_family=mixed_family),
+        )
+
+    def set_union(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return a group-by aggregation with the minimal CIDR list covering the group's CIDRs and ``other``.
+
+        ``other`` is a CIDR column aggregated over the same group, a CIDR literal or a list of CIDRs.
+        """
+        return self._set_operation("union", other, parse, on_error)
+
+    def set_intersection(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return a group-by aggregation with the minimal CIDR list covering addresses in both the group and ``other``."""
+        return self._set_operation("intersection", other, parse, on_error)
+
+    def set_difference(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return a group-by aggregation with the minimal CIDR list covering the group's addresses missing from ``other``."""
+        return self._set_operation("difference", other, parse, on_error)
+
+    def set_symmetric_difference(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr:
+        """Return a group-by aggregation with the minimal CIDR list covering addresses in exactly one of the group and ``other``."""
+        return self._set_operation("symmetric_difference", other, parse, on_error)
+
+    def _set_operation(
+        self, operation: str, other: IntoExpr, parse: ParseMode, on_error: OnError
+    ) -> pl.Expr:
+        return _plugin_scalar_agg(
+            "cidr_set_operation",
+            (self._expr, _to_expr(other)),
+            _cidr_kwargs(parse, on_error, operation=operation),
+        )
+
+    def encode(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression encoding each CIDR as a ``{family, addr_hi, addr_lo, prefix}`` struct.
+
+        Every ``cidr`` expression accepts the encoded form in place of strings, which avoids
+        re-parsing the same column in pipelines chaining several expressions.
+        """
+        return _plugin_expr("cidr_encode", (self._expr,), _cidr_kwargs(parse, on_error))
+
+    def decode(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr:
+        """Return an expression converting encoded CIDRs back to their string form."""
+        return _plugin_expr("cidr_decode", (self._expr,), _cidr_kwargs(parse, on_error))
+
+
+@register_expr_namespace("ip")
+class IpNamespace:
+    """Expressions over bare IP addresses.
 
-    def supernet(self) -> pl.Expr:
-        """Return a group-by aggregation producing the minimal supernet per group."""
-        return _plugin_agg("cidr_supernet", (self._expr,))
+    Unlike ``cidr``, prefix notation such as ``10.0.0.1/24`` is treated as invalid unless
+    ``allow_prefix=True``, in which case the address part is used.
+    """
+
+    def __init__(self, expr: pl.Expr):
+        self._expr = expr
+
+    def is_valid(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression indicating whether each value in ``self`` is an IP address."""
+        return _plugin_expr("ip_is_valid", (self._expr,), {"allow_prefix": allow_prefix})
+
+    def version(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return an expression indicating whether each address is IPv4 or IPv6."""
+        return _plugin_expr("ip_version", (self._expr,), {"allow_prefix": allow_prefix})
+
+    def is_private(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging RFC 1918 IPv4 and unique local IPv6 addresses."""
+        return self._is_class("private", allow_prefix)
+
+    def is_loopback(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging loopback addresses."""
+        return self._is_class("loopback", allow_prefix)
+
+    def is_link_local(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging link-local addresses."""
+        return self._is_class("link_local", allow_prefix)
+
+    def is_multicast(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging multicast addresses."""
+        return self._is_class("multicast", allow_prefix)
+
+    def is_unspecified(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging the unspecified addresses ``0.0.0.0`` and ``::``."""
+        return self._is_class("unspecified", allow_prefix)
+
+    def is_documentation(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging addresses reserved for documentation."""
+        return self._is_class("documentation", allow_prefix)
+
+    def is_global(self, allow_prefix: bool = False) -> pl.Expr:
+        """Return a boolean expression flagging globally reachable unicast addresses.
+
+        Addresses in ranges the IANA special-purpose registries mark as not globally reachable are
+        excluded, as in :attr:`ipaddress.IPv4Address.is_global`, and so is multicast.
+        """
+        return self._is_class("global", allow_prefix)
+
+    def format(self, style: AddressStyle = "compressed", allow_prefix: bool = False) -> pl.Expr:
+        """Return an expression formatting each address as ``"compressed"``, ``"exploded"`` or ``"reverse_pointer"`` text."""
+        return _plugin_expr(
+            "ip_format", (self._expr,), {"style": style, "allow_prefix": allow_prefix}
+        )
+
+    def to_int(self, output: IntegerOutput = "int64", allow_prefix: bool = False) -> pl.Expr:
+        """Return an expression converting each IP address in ``self`` to its integer value.
+
+        ``output`` is ``"int64"`` (null for IPv6) or ``"struct"`` holding the ``addr_hi``/``addr_lo``
+        halves of the 128-bit value.
+        """
+        return _plugin_expr(
+            "ip_to_int", (self._expr,), {"output": output, "allow_prefix": allow_prefix}
+        )
+
+    def add(self, offset: IntoExpr, allow_prefix: bool = False) -> pl.Expr:
+        """Return an expression moving each IP address in ``self`` by ``offset``.
+
+        Results leaving the address space of the family yield null.
+        """
+        return _plugin_expr(
+            "ip_add", (self._expr, _to_expr(offset)), {"allow_prefix": allow_prefix}
+        )
+
+    def from_int(self, version: Literal[4, 6] = 4) -> pl.Expr:
+        """Return an expression converting integers or ``addr_hi``/``addr_lo`` structs to IP address strings.
+
+        Values out of range for IPv4 when ``version`` is ``4`` yield null.
+        """
+        return _plugin_expr("ip_from_int", (self._expr,), {"version": version})
+
+    def _is_class(self, address_class: str, allow_prefix: bool) -> pl.Expr:
+        return _plugin_expr(
+            "ip_is_class", (self._expr,), {"class": address_class, "allow_prefix": allow_prefix}
+        )
+
+
+def from_range(start: IntoExpr, end: IntoExpr, on_error: OnError = "null") -> pl.Expr:
+    """Return an expression listing the minimal CIDRs covering each inclusive ``start``-``end`` address range.
+
+    Strings are taken as column names, as in Polars' own functions; wrap addresses in ``pl.lit``.
+    Either bound may be a literal, broadcast against the other. Rows mixing families or with
+    ``start`` after ``end`` yield null, as do unparseable addresses unless ``on_error`` is
+    ``"raise"``, which fails with the offending value and row.
+    """
+    bounds = [
+        pl.col(bound) if isinstance(bound, str) else _to_expr(bound) for bound in (start, end)
+    ]
+    return _plugin_expr("cidr_from_range", bounds, {"on_error": on_error})
+
+
+def lpm_join(
+    left: pl.DataFrame | pl.LazyFrame,
+    right: pl.DataFrame | pl.LazyFrame,
+    *,
+    left_on: str,
+    right_on: str,
+    suffix: str = "_right",
+    parse: ParseMode = "strict",
+    on_error: OnError = "null",
+) -> pl.DataFrame | pl.LazyFrame:
+    """Left join ``right`` onto ``left`` on the longest ``right_on`` prefix containing ``left_on``.
+
+    The ``right_on`` prefixes are collected once to build the lookup trie; the result is lazy
+    when ``left`` is a :class:`polars.LazyFrame`. ``parse`` and ``on_error`` apply to both
+    columns, as in :class:`CidrNamespace`.
+    """
+    index_name = "__lpm_index"
+    right_lazy = right.lazy()
+    prefixes = right_lazy.select(pl.col(right_on)).collect().to_series()
+    lookup = pl.col(left_on).cidr.lpm_lookup(prefixes, parse=parse, on_error=on_error)
+
+    joined = (
+        left.lazy()
+        .with_columns(lookup.alias(index_name))
+        .join(right_lazy.with_row_index(index_name), on=index_name, how="left", suffix=suffix)
+        .drop(index_name)
+    )
+    return joined if isinstance(left, pl.LazyFrame) else joined.collect()
 
 
-__all__ = ["CidrNamespace", "__version__"]
+__all__ = ["CidrNamespace", "IpNamespace", "__version__", "from_range", "lpm_join"]
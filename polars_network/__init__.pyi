@@ -1,19 +1,47 @@
 from __future__ import annotations
 
 from pathlib import Path
-from typing import Sequence
+from typing import Any, Literal, Sequence
 
 import polars as pl
 from polars._typing import IntoExpr
 
+AddressOutput = Literal["int64", "string", "struct"]
+IntegerOutput = Literal["int64", "struct"]
+AddressStyle = Literal["compressed", "exploded", "reverse_pointer"]
+MixedFamily = Literal["null", "split", "mapped"]
+CountOutput = Literal["uint64", "float64", "string"]
+Notation = Literal["netmask", "wildcard", "glob", "range"]
+ParseMode = Literal["strict", "lenient"] | Notation | Sequence[Notation]
+OnError = Literal["null", "raise", "skip"]
+
 PLUGIN_PATH: Path
 __version__: str
 
+_NOTATIONS: tuple[Notation, ...]
+
+
+def _plugin_expr(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr: ...
+
+
+def _plugin_window(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr: ...
+
+
+def _plugin_agg(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr: ...
+
 
-def _plugin_expr(function_name: str, args: Sequence[pl.Expr]) -> pl.Expr: ...
+def _plugin_scalar_agg(
+    function_name: str, args: Sequence[pl.Expr], kwargs: dict[str, Any] | None = None
+) -> pl.Expr: ...
 
 
-def _plugin_agg(function_name: str, args: Sequence[pl.Expr]) -> pl.Expr: ...
+def _cidr_kwargs(parse: ParseMode, on_error: OnError, **kwargs: Any) -> dict[str, Any]: ...
 
 
 def _to_expr(value: IntoExpr) -> pl.Expr: ...
@@ -24,28 +52,227 @@ class CidrNamespace:
 
     def __init__(self, expr: pl.Expr) -> None: ...
 
-    def contains(self, other: IntoExpr) -> pl.Expr: ...
+    def contains(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def subnet_of(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def contains_any(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def subnet_of_any(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def overlaps(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def intersection(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def exclude(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def lpm_lookup(
+        self,
+        prefixes: IntoExpr | pl.Series,
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def is_root(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def parent(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def depth(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def overlapping_with(
+        self, values: bool = False, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def network_address(
+        self,
+        output: AddressOutput = "int64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def broadcast_address(
+        self,
+        output: AddressOutput = "int64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def network(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def broadcast(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def first_host(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def last_host(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def to_range(
+        self,
+        output: AddressOutput = "string",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def nth_host(
+        self, n: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def next(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def previous(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def num_addresses(
+        self,
+        output: CountOutput = "uint64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def num_hosts(
+        self,
+        output: CountOutput = "uint64",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def hosts(
+        self, limit: int = 65_536, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def netmask(
+        self, binary: IntoExpr = False, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def subnets(
+        self,
+        new_prefix: IntoExpr,
+        max_count: int = 65_536,
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def subnets_count(
+        self, new_prefix: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def version(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def is_valid(self, allow_host_bits: bool = False, parse: ParseMode = "strict") -> pl.Expr: ...
+
+    def parse_error(
+        self, allow_host_bits: bool = False, parse: ParseMode = "strict"
+    ) -> pl.Expr: ...
+
+    def has_host_bits(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def normalize(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def supernet(
+        self,
+        mixed_family: MixedFamily = "null",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def collapse(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def num_unique_addresses(
+        self,
+        output: CountOutput = "uint64",
+        mixed_family: MixedFamily = "null",
+        parse: ParseMode = "strict",
+        on_error: OnError = "null",
+    ) -> pl.Expr: ...
+
+    def set_union(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def set_intersection(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def set_difference(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def set_symmetric_difference(
+        self, other: IntoExpr, parse: ParseMode = "strict", on_error: OnError = "null"
+    ) -> pl.Expr: ...
+
+    def _set_operation(
+        self, operation: str, other: IntoExpr, parse: ParseMode, on_error: OnError
+    ) -> pl.Expr: ...
+
+    def encode(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+    def decode(self, parse: ParseMode = "strict", on_error: OnError = "null") -> pl.Expr: ...
+
+
+class IpNamespace:
+    _expr: pl.Expr
+
+    def __init__(self, expr: pl.Expr) -> None: ...
+
+    def is_valid(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def version(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_private(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_loopback(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_link_local(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_multicast(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_unspecified(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_documentation(self, allow_prefix: bool = False) -> pl.Expr: ...
+
+    def is_global(self, allow_prefix: bool = False) -> pl.Expr: ...
 
-    def subnet_of(self, other: IntoExpr) -> pl.Expr: ...
+    def format(self, style: AddressStyle = "compressed", allow_prefix: bool = False) -> pl.Expr: ...
 
-    def contains_any(self, other: IntoExpr) -> pl.Expr: ...
+    def to_int(self, output: IntegerOutput = "int64", allow_prefix: bool = False) -> pl.Expr: ...
 
-    def subnet_of_any(self, other: IntoExpr) -> pl.Expr: ...
+    def add(self, offset: IntoExpr, allow_prefix: bool = False) -> pl.Expr: ...
 
-    def is_root(self) -> pl.Expr: ...
+    def from_int(self, version: Literal[4, 6] = 4) -> pl.Expr: ...
 
-    def network_address(self) -> pl.Expr: ...
+    def _is_class(self, address_class: str, allow_prefix: bool) -> pl.Expr: ...
 
-    def broadcast_address(self) -> pl.Expr: ...
 
-    def netmask(self, binary: IntoExpr = False) -> pl.Expr: ...
+def from_range(start: IntoExpr, end: IntoExpr, on_error: OnError = "null") -> pl.Expr: ...
 
-    def version(self) -> pl.Expr: ...
 
-    def supernet(self) -> pl.Expr: ...
+def lpm_join(
+    left: pl.DataFrame | pl.LazyFrame,
+    right: pl.DataFrame | pl.LazyFrame,
+    *,
+    left_on: str,
+    right_on: str,
+    suffix: str = "_right",
+    parse: ParseMode = "strict",
+    on_error: OnError = "null",
+) -> pl.DataFrame | pl.LazyFrame: ...
 
 
 __all__: list[str]
 
 Expr = pl.Expr
 Expr.cidr: CidrNamespace = ...  # type: ignore[misc]
+Expr.ip: IpNamespace = ...  # type: ignore[misc]
@@ -1,30 +1,246 @@
 use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
 use polars::prelude::*;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use pyo3::prelude::*;
 use pyo3_polars::derive::polars_expr;
+use serde::Deserialize;
+
+use crate::notation::{parse_error_reason, parse_network, Notation};
+use crate::ranges::{network_range, range_to_networks, AddressCount, RangeSet};
+use crate::trie::PrefixTrie;
+
+const ENCODED_FAMILY: &str = "family";
+const ENCODED_ADDR_HI: &str = "addr_hi";
+const ENCODED_ADDR_LO: &str = "addr_lo";
+const ENCODED_PREFIX: &str = "prefix";
+
+const SUPERNET_V4: &str = "v4";
+const SUPERNET_V6: &str = "v6";
+const RANGE_START: &str = "start";
+const RANGE_END: &str = "end";
+
+/// Parsing options shared by every `cidr_*` expression.
+#[derive(Deserialize, Default)]
+pub struct ParseOptions {
+    /// Notations accepted besides `addr/prefix`, tried in order.
+    #[serde(default)]
+    notations: Vec<Notation>,
+    #[serde(default)]
+    on_error: OnError,
+}
+
+/// Handling of values that fail to parse.
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    /// Treat the value as null, which also nulls a list argument row holding it.
+    #[default]
+    Null,
+    /// Fail with the offending value and its row.
+    Raise,
+    /// Treat the value as null, dropping it from list argument rows.
+    Skip,
+}
+
+/// Representation used by expressions returning addresses.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressOutput {
+    /// IPv4 addresses as integers; IPv6 addresses overflow and yield nulls.
+    Int64,
+    /// Canonical address text for both families.
+    String,
+    /// `{addr_hi, addr_lo}` halves of the 128-bit address, IPv4 in the low half.
+    Struct,
+}
+
+#[derive(Deserialize)]
+pub struct AddressKwargs {
+    output: AddressOutput,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+#[derive(Deserialize)]
+pub struct FromIntKwargs {
+    version: u8,
+}
+
+#[derive(Deserialize)]
+pub struct FromRangeKwargs {
+    #[serde(default)]
+    on_error: OnError,
+}
+
+/// Handling of groups mixing IPv4 and IPv6 networks in `cidr.supernet` and
+/// `cidr.num_unique_addresses`.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum MixedFamily {
+    /// Yield null for mixed groups.
+    Null,
+    /// Return a `{v4, v6}` struct with one supernet per family.
+    Split,
+    /// Map IPv4 networks into `::ffff:0:0/96` and compute a single IPv6 result.
+    Mapped,
+}
+
+#[derive(Deserialize)]
+pub struct SupernetKwargs {
+    mixed_family: MixedFamily,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+#[derive(Deserialize)]
+pub struct SubnetsKwargs {
+    /// Largest number of subnets a single row may expand to.
+    max_count: u64,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+/// Representation used by expressions returning address counts.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CountOutput {
+    /// Exact counts, null beyond 2^64 - 1.
+    UInt64,
+    /// Approximate counts for every network size.
+    Float64,
+    /// Exact decimal text for every network size.
+    String,
+}
+
+#[derive(Deserialize)]
+pub struct CountKwargs {
+    output: CountOutput,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+#[derive(Deserialize)]
+pub struct UniqueCountKwargs {
+    output: CountOutput,
+    mixed_family: MixedFamily,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+#[derive(Deserialize)]
+pub struct HostsKwargs {
+    /// Largest number of host addresses a single row may expand to.
+    limit: u64,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+#[derive(Deserialize)]
+pub struct OverlappingKwargs {
+    /// Return the overlapping networks instead of their row indices.
+    values: bool,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+/// Address-set operations available to `cidr.set_*` aggregations.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SetOperation {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+#[derive(Deserialize)]
+pub struct SetOperationKwargs {
+    operation: SetOperation,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+#[derive(Deserialize)]
+pub struct ValidateKwargs {
+    /// Accept networks such as `10.1.2.3/8` whose address has host bits set.
+    allow_host_bits: bool,
+    #[serde(flatten)]
+    parse: ParseOptions,
+}
+
+/// Options shared by `ip` expressions parsing bare addresses.
+#[derive(Deserialize)]
+pub struct IpKwargs {
+    /// Accept `addr/prefix` notation and keep the address part.
+    #[serde(default)]
+    allow_prefix: bool,
+}
+
+#[derive(Deserialize)]
+pub struct IpToIntKwargs {
+    output: AddressOutput,
+    #[serde(default)]
+    allow_prefix: bool,
+}
+
+/// Special-purpose address ranges recognised by `ip.is_*` expressions.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressClass {
+    Private,
+    Loopback,
+    LinkLocal,
+    Multicast,
+    Unspecified,
+    Documentation,
+    Global,
+}
+
+#[derive(Deserialize)]
+pub struct IpClassKwargs {
+    class: AddressClass,
+    #[serde(default)]
+    allow_prefix: bool,
+}
+
+/// Text representations produced by `ip.format`.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressStyle {
+    Compressed,
+    Exploded,
+    ReversePointer,
+}
+
+#[derive(Deserialize)]
+pub struct IpFormatKwargs {
+    style: AddressStyle,
+    #[serde(default)]
+    allow_prefix: bool,
+}
 
 pub fn register(_module: &Bound<'_, PyModule>) -> PyResult<()> {
     Ok(())
 }
 
 #[polars_expr(output_type=Boolean)]
-pub fn cidr_contains(inputs: &[Series]) -> PolarsResult<Series> {
+pub fn cidr_contains(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
         inputs.len() == 2,
         ComputeError: "cidr.contains expects 2 arguments (expression, cidr expression or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let needle = resolve_network_argument(&inputs[1], "needle", len)?;
+    let networks = parse_network_series(series, &kwargs)?;
+    let needle = resolve_network_argument(&inputs[1], "needle", len, &kwargs)?;
 
     let mut builder = BooleanChunkedBuilder::new(name, len);
-    for (idx, value) in series.into_iter().enumerate() {
-        match (parse_optional_network(value), needle.value_at(idx)) {
+    for (idx, network) in networks.iter().enumerate() {
+        match (network, needle.value_at(idx)) {
             (Some(network), Some(needle_network)) => {
-                builder.append_value(network_contains(&network, needle_network))
+                builder.append_value(network_contains(network, needle_network))
             }
             _ => builder.append_null(),
         }
@@ -34,22 +250,23 @@ pub fn cidr_contains(inputs: &[Series]) -> PolarsResult<Series> {
 }
 
 #[polars_expr(output_type=Boolean)]
-pub fn cidr_subnet_of(inputs: &[Series]) -> PolarsResult<Series> {
+pub fn cidr_subnet_of(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
         inputs.len() == 2,
         ComputeError: "cidr.subnet_of expects 2 arguments (expression, cidr expression or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let supernet = resolve_network_argument(&inputs[1], "supernet", len)?;
+    let networks = parse_network_series(series, &kwargs)?;
+    let supernet = resolve_network_argument(&inputs[1], "supernet", len, &kwargs)?;
 
     let mut builder = BooleanChunkedBuilder::new(name, len);
-    for (idx, value) in series.into_iter().enumerate() {
-        match (parse_optional_network(value), supernet.value_at(idx)) {
+    for (idx, network) in networks.iter().enumerate() {
+        match (network, supernet.value_at(idx)) {
             (Some(network), Some(supernet_network)) => {
-                builder.append_value(network_contains(supernet_network, &network))
+                builder.append_value(network_contains(supernet_network, network))
             }
             _ => builder.append_null(),
         }
@@ -59,24 +276,29 @@ pub fn cidr_subnet_of(inputs: &[Series]) -> PolarsResult<Series> {
 }
 
 #[polars_expr(output_type=Boolean)]
-pub fn cidr_contains_any(inputs: &[Series]) -> PolarsResult<Series> {
+pub fn cidr_contains_any(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
         inputs.len() == 2,
         ComputeError: "cidr.contains_any expects 2 arguments (expression, cidr list expression or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let subnets = resolve_network_list_argument(&inputs[1], "subnets", len)?;
+    let networks = parse_network_series(series, &kwargs)?;
+    let subnets = resolve_network_list_argument(&inputs[1], "subnets", len, &kwargs)?;
+    let trie = subnets.shared_values().map(PrefixTrie::from_networks);
 
     let mut builder = BooleanChunkedBuilder::new(name, len);
-    for (idx, value) in series.into_iter().enumerate() {
-        match (parse_optional_network(value), subnets.values_at(idx)) {
+    for (idx, network) in networks.iter().enumerate() {
+        match (network, subnets.values_at(idx)) {
             (Some(network), Some(candidate_subnets)) => {
-                let contains_any = candidate_subnets
-                    .iter()
-                    .any(|candidate| network_contains(&network, candidate));
+                let contains_any = match &trie {
+                    Some(trie) => trie.contains_subnet_of(network),
+                    None => candidate_subnets
+                        .iter()
+                        .any(|candidate| network_contains(network, candidate)),
+                };
                 builder.append_value(contains_any)
             }
             _ => builder.append_null(),
@@ -87,24 +309,29 @@ pub fn cidr_contains_any(inputs: &[Series]) -> PolarsResult<Series> {
 }
 
 #[polars_expr(output_type=Boolean)]
-pub fn cidr_subnet_of_any(inputs: &[Series]) -> PolarsResult<Series> {
+pub fn cidr_subnet_of_any(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
         inputs.len() == 2,
         ComputeError: "cidr.subnet_of_any expects 2 arguments (expression, cidr list expression or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let supernets = resolve_network_list_argument(&inputs[1], "supernets", len)?;
+    let networks = parse_network_series(series, &kwargs)?;
+    let supernets = resolve_network_list_argument(&inputs[1], "supernets", len, &kwargs)?;
+    let trie = supernets.shared_values().map(PrefixTrie::from_networks);
 
     let mut builder = BooleanChunkedBuilder::new(name, len);
-    for (idx, value) in series.into_iter().enumerate() {
-        match (parse_optional_network(value), supernets.values_at(idx)) {
+    for (idx, network) in networks.iter().enumerate() {
+        match (network, supernets.values_at(idx)) {
             (Some(network), Some(candidate_supernets)) => {
-                let is_subnet = candidate_supernets
-                    .iter()
-                    .any(|candidate| network_contains(candidate, &network));
+                let is_subnet = match &trie {
+                    Some(trie) => trie.contains_supernet_of(network),
+                    None => candidate_supernets
+                        .iter()
+                        .any(|candidate| network_contains(candidate, network)),
+                };
                 builder.append_value(is_subnet)
             }
             _ => builder.append_null(),
@@ -115,297 +342,1546 @@ pub fn cidr_subnet_of_any(inputs: &[Series]) -> PolarsResult<Series> {
 }
 
 #[polars_expr(output_type=Boolean)]
-pub fn cidr_is_root(inputs: &[Series]) -> PolarsResult<Series> {
+pub fn cidr_overlaps(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
-        inputs.len() == 1,
-        ComputeError: "cidr.is_root expects 1 argument (expression)"
+        inputs.len() == 2,
+        ComputeError: "cidr.overlaps expects 2 arguments (expression, cidr expression, list or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let networks = series.into_iter().map(parse_optional_network).collect::<Vec<_>>();
+    let networks = parse_network_series(series, &kwargs)?;
 
     let mut builder = BooleanChunkedBuilder::new(name, len);
-    for (idx, current) in networks.iter().enumerate() {
-        match current {
-            Some(network) => {
-                let mut has_parent = false;
-                for (other_idx, candidate) in networks.iter().enumerate() {
-                    if other_idx == idx {
-                        continue;
-                    }
-
-                    if let Some(candidate_network) = candidate {
-                        if candidate_network != network
-                            && network_contains(candidate_network, network)
-                        {
-                            has_parent = true;
-                            break;
+    if inputs[1].list().is_ok() {
+        let others = resolve_network_list_argument(&inputs[1], "other", len, &kwargs)?;
+        let trie = others.shared_values().map(PrefixTrie::from_networks);
+
+        for (idx, network) in networks.iter().enumerate() {
+            match (network, others.values_at(idx)) {
+                (Some(network), Some(candidates)) => {
+                    let overlaps = match &trie {
+                        Some(trie) => {
+                            trie.contains_subnet_of(network) || trie.contains_supernet_of(network)
                         }
-                    }
+                        None => candidates
+                            .iter()
+                            .any(|candidate| networks_overlap(network, candidate)),
+                    };
+                    builder.append_value(overlaps)
                 }
+                _ => builder.append_null(),
+            }
+        }
+    } else {
+        let other = resolve_network_argument(&inputs[1], "other", len, &kwargs)?;
 
-                builder.append_value(!has_parent);
+        for (idx, network) in networks.iter().enumerate() {
+            match (network, other.value_at(idx)) {
+                (Some(network), Some(other_network)) => {
+                    builder.append_value(networks_overlap(network, other_network))
+                }
+                _ => builder.append_null(),
             }
-            None => builder.append_null(),
         }
     }
 
     Ok(builder.finish().into_series())
 }
 
-#[polars_expr(output_type=Int64)]
-pub fn cidr_network_address(inputs: &[Series]) -> PolarsResult<Series> {
+#[polars_expr(output_type=String)]
+pub fn cidr_intersection(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
-        inputs.len() == 1,
-        ComputeError: "cidr.network_address expects 1 argument (expression)"
+        inputs.len() == 2,
+        ComputeError: "cidr.intersection expects 2 arguments (expression, cidr expression or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let mut values = Vec::with_capacity(len);
+    let networks = parse_network_series(series, &kwargs)?;
+    let other = resolve_network_argument(&inputs[1], "other", len, &kwargs)?;
+
+    let values = networks.iter().enumerate().map(|(idx, network)| {
+        let (network, other_network) = (network.as_ref()?, other.value_at(idx)?);
+        let intersection = if network_contains(network, other_network) {
+            other_network
+        } else if network_contains(other_network, network) {
+            network
+        } else {
+            return None;
+        };
+        Some(canonical_network(intersection).to_string())
+    });
 
-    for value in series.into_iter() {
-        let entry = parse_optional_network(value)
-            .and_then(|network| network_address_numeric(&network));
-        values.push(entry);
-    }
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
 
-    let chunked = Int64Chunked::from_iter(values);
-    let mut series = chunked.into_series();
-    series.rename(name);
-    Ok(series)
+#[polars_expr(output_type=String)]
+pub fn cidr_parent(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.parent expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+
+    let parents = network_parents(&networks)
+        .into_iter()
+        .map(|parent| parent.map(|network| network.to_string()));
+
+    let chunked = StringChunked::from_iter(parents);
+    Ok(chunked.with_name(name).into_series())
 }
 
 #[polars_expr(output_type=Int64)]
-pub fn cidr_broadcast_address(inputs: &[Series]) -> PolarsResult<Series> {
+pub fn cidr_depth(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
         inputs.len() == 1,
-        ComputeError: "cidr.broadcast_address expects 1 argument (expression)"
+        ComputeError: "cidr.depth expects 1 argument (expression)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+
+    let depths = network_depths(&networks)
+        .into_iter()
+        .map(|depth| depth.map(|depth| depth as i64));
+
+    let chunked = Int64Chunked::from_iter(depths);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type_func_with_kwargs=overlapping_output)]
+pub fn cidr_overlapping_with(inputs: &[Series], kwargs: OverlappingKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.overlapping_with expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
-    let mut values = Vec::with_capacity(len);
+    let networks = parse_network_series(series, &kwargs.parse)?;
+    let overlaps = overlapping_rows(&networks);
+
+    if kwargs.values {
+        let rows = networks
+            .iter()
+            .zip(&overlaps)
+            .map(|(network, rows)| {
+                network.map(|_| rows.iter().filter_map(|idx| networks[*idx]).collect())
+            })
+            .collect::<Vec<_>>();
+        return Ok(network_list_series(name, &rows));
+    }
 
-    for value in series.into_iter() {
-        let entry = parse_optional_network(value)
-            .and_then(|network| broadcast_address_numeric(&network));
-        values.push(entry);
+    let mut builder =
+        ListPrimitiveChunkedBuilder::<UInt32Type>::new(name, len, len, DataType::UInt32);
+    for (network, rows) in networks.iter().zip(&overlaps) {
+        match network {
+            Some(_) => {
+                let indices = rows.iter().map(|idx| *idx as u32).collect::<Vec<_>>();
+                builder.append_slice(&indices)
+            }
+            None => builder.append_null(),
+        }
     }
 
-    let chunked = Int64Chunked::from_iter(values);
-    let mut series = chunked.into_series();
-    series.rename(name);
-    Ok(series)
+    Ok(builder.finish().into_series())
 }
 
-#[polars_expr(output_type=Int64)]
-pub fn cidr_netmask(inputs: &[Series]) -> PolarsResult<Series> {
+#[polars_expr(output_type_func=string_list_output)]
+pub fn cidr_exclude(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
-        inputs.len() == 1 || inputs.len() == 2,
-        ComputeError: "cidr.netmask expects 1 or 2 arguments (expression, optional binary flag)"
+        inputs.len() == 2,
+        ComputeError: "cidr.exclude expects 2 arguments (expression, cidr list expression or literal)"
     );
 
-    let series = inputs[0].str()?;
+    let series = &inputs[0];
     let len = series.len();
     let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+    let excluded = resolve_network_list_argument(&inputs[1], "excluded", len, &kwargs)?;
+    let shared = excluded.shared_values().map(RangeSet::from_networks);
 
-    let binary = if inputs.len() == 2 {
-        resolve_bool_argument(&inputs[1], "binary", len)?
-    } else {
-        BoolArgument::Literal(false)
-    };
-
-    let mut results: Vec<Option<i64>> = Vec::with_capacity(len);
-    for (idx, value) in series.into_iter().enumerate() {
-        let entry = match (parse_optional_network(value), binary.value_at(idx)) {
-            (Some(network), Some(is_binary)) => {
-                if is_binary {
-                    match network {
-                        IpNetwork::V4(net) => Some(i64::from(u32::from(net.mask()))),
-                        IpNetwork::V6(_) => None,
-                    }
-                } else {
-                    Some(i64::from(network.prefix()))
-                }
-            }
-            (Some(_), None) => None,
+    let mut rows = Vec::with_capacity(len);
+    for (idx, network) in networks.iter().enumerate() {
+        let row = match (network, excluded.values_at(idx)) {
+            (Some(network), Some(candidates)) => Some(match &shared {
+                Some(removed) => removed.subtract_from(network),
+                None => RangeSet::from_networks(candidates).subtract_from(network),
+            }),
             _ => None,
         };
-
-        results.push(entry);
+        rows.push(row);
     }
 
-    let chunked = Int64Chunked::from_iter(results);
-    Ok(chunked.with_name(name).into_series())
+    Ok(network_list_series(name, &rows))
 }
 
-#[polars_expr(output_type=Int64)]
-pub fn cidr_version(inputs: &[Series]) -> PolarsResult<Series> {
+#[polars_expr(output_type=UInt32)]
+pub fn cidr_lpm_lookup(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
-        inputs.len() == 1,
-        ComputeError: "cidr.version expects 1 argument (expression)"
+        inputs.len() == 2,
+        ComputeError: "cidr.lpm_lookup expects 2 arguments (expression, cidr list literal or column)"
     );
 
-    let series = inputs[0].str()?;
-    let len = series.len();
+    let series = &inputs[0];
     let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+    let prefixes = resolve_network_table_argument(&inputs[1], "prefixes", &kwargs)?;
 
-    let mut values = Vec::with_capacity(len);
-    for value in series.into_iter() {
-        let entry = parse_optional_network(value).map(|network| match network {
-            IpNetwork::V4(_) => 4,
-            IpNetwork::V6(_) => 6,
-        });
-        values.push(entry.map(i64::from));
+    let mut trie = PrefixTrie::new();
+    for (idx, prefix) in prefixes.iter().enumerate() {
+        if let Some(prefix) = prefix {
+            trie.insert(prefix, idx);
+        }
     }
 
-    let chunked = Int64Chunked::from_iter(values);
+    let values = networks.iter().map(|network| {
+        network
+            .as_ref()
+            .and_then(|network| trie.longest_match(network))
+            .map(|idx| idx as u32)
+    });
+
+    let chunked = UInt32Chunked::from_iter(values);
     Ok(chunked.with_name(name).into_series())
 }
 
-#[polars_expr(output_type=String)]
-pub fn cidr_supernet(inputs: &[Series]) -> PolarsResult<Series> {
+#[polars_expr(output_type=Boolean)]
+pub fn cidr_is_root(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
         inputs.len() == 1,
-        ComputeError: "cidr.supernet expects 1 argument (expression)"
+        ComputeError: "cidr.is_root expects 1 argument (expression)"
     );
 
     let series = &inputs[0];
+    let len = series.len();
     let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
 
-    match series.dtype() {
-        DataType::String => {
-            let chunked = series.str()?;
-            let mut networks = Vec::with_capacity(chunked.len());
-            for value in chunked.into_iter() {
-                if let Some(network) = parse_optional_network(value) {
-                    networks.push(network);
-                }
-            }
+    let has_parent = networks_with_parent(&networks);
 
-            let result = minimal_supernet(&networks).map(|net| net.to_string());
-            let chunked = StringChunked::from_iter([result]);
-            Ok(chunked.with_name(name).into_series())
+    let mut builder = BooleanChunkedBuilder::new(name, len);
+    for (network, has_parent) in networks.iter().zip(has_parent) {
+        match network {
+            Some(_) => builder.append_value(!has_parent),
+            None => builder.append_null(),
         }
-        dtype => polars_bail!(
-            ComputeError: "cidr.supernet expects UTF-8 values (got {:?})",
-            dtype
-        ),
     }
-}
 
-enum NetworkArgument {
-    Literal(IpNetwork),
-    Series(Vec<Option<IpNetwork>>),
+    Ok(builder.finish().into_series())
 }
 
-impl NetworkArgument {
-    fn value_at(&self, idx: usize) -> Option<&IpNetwork> {
-        match self {
-            NetworkArgument::Literal(network) => Some(network),
-            NetworkArgument::Series(values) => values.get(idx).and_then(|value| value.as_ref()),
-        }
-    }
-}
+#[polars_expr(output_type_func_with_kwargs=address_output)]
+pub fn cidr_network_address(inputs: &[Series], kwargs: AddressKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.network_address expects 1 argument (expression)"
+    );
 
-fn resolve_network_argument(
-    series: &Series,
-    arg_name: &str,
-    expected_len: usize,
-) -> PolarsResult<NetworkArgument> {
-    let chunked = series.str()?;
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let addresses = parse_network_series(series, &kwargs.parse)?
+        .into_iter()
+        .map(|network| network.map(|network| network_address(&network)))
+        .collect::<Vec<_>>();
 
-    if chunked.len() == 1 {
-        let value = chunked
-            .get(0)
-            .ok_or_else(|| polars_err!(ComputeError: "{} argument cannot be null", arg_name))?;
+    address_series(name, &addresses, kwargs.output)
+}
+
+#[polars_expr(output_type_func_with_kwargs=address_output)]
+pub fn cidr_broadcast_address(inputs: &[Series], kwargs: AddressKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.broadcast_address expects 1 argument (expression)"
+    );
 
-        let network = value.parse::<IpNetwork>().map_err(
-            |err| polars_err!(ComputeError: "invalid {} CIDR '{}': {}", arg_name, value, err),
-        )?;
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let addresses = parse_network_series(series, &kwargs.parse)?
+        .into_iter()
+        .map(|network| network.map(|network| broadcast_address(&network)))
+        .collect::<Vec<_>>();
 
-        return Ok(NetworkArgument::Literal(network));
-    }
+    address_series(name, &addresses, kwargs.output)
+}
 
+#[polars_expr(output_type=String)]
+pub fn cidr_first_host(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
     polars_ensure!(
-        chunked.len() == expected_len,
-        ComputeError: "{} argument must be a literal or expression with {} rows (got {})",
-        arg_name,
-        expected_len,
-        chunked.len()
+        inputs.len() == 1,
+        ComputeError: "cidr.first_host expects 1 argument (expression)"
     );
 
-    let parsed_values = chunked
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let addresses = parse_network_series(series, &kwargs)?
         .into_iter()
-        .map(parse_optional_network)
+        .map(|network| network.map(|network| first_host(&network)))
         .collect::<Vec<_>>();
 
-    Ok(NetworkArgument::Series(parsed_values))
+    address_series(name, &addresses, AddressOutput::String)
 }
 
-enum NetworkListArgument {
-    Literal(Vec<IpNetwork>),
-    Column(Vec<IpNetwork>),
-    Series(Vec<Option<Vec<IpNetwork>>>),
-}
+#[polars_expr(output_type=String)]
+pub fn cidr_last_host(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.last_host expects 1 argument (expression)"
+    );
 
-enum BoolArgument {
-    Literal(bool),
-    Series(Vec<Option<bool>>),
-}
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let addresses = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .map(|network| network.map(|network| last_host(&network)))
+        .collect::<Vec<_>>();
 
-impl BoolArgument {
-    fn value_at(&self, idx: usize) -> Option<bool> {
-        match self {
-            BoolArgument::Literal(value) => Some(*value),
-            BoolArgument::Series(values) => values.get(idx).copied().flatten(),
-        }
-    }
+    address_series(name, &addresses, AddressOutput::String)
 }
 
-impl NetworkListArgument {
-    fn values_at(&self, idx: usize) -> Option<&[IpNetwork]> {
-        match self {
-            NetworkListArgument::Literal(values) | NetworkListArgument::Column(values) => {
-                Some(values.as_slice())
-            }
-            NetworkListArgument::Series(rows) => rows
+#[polars_expr(output_type_func_with_kwargs=range_output)]
+pub fn cidr_to_range(inputs: &[Series], kwargs: AddressKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.to_range expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs.parse)?;
+
+    let starts = networks
+        .iter()
+        .map(|network| network.as_ref().map(network_address))
+        .collect::<Vec<_>>();
+    let ends = networks
+        .iter()
+        .map(|network| network.as_ref().map(broadcast_address))
+        .collect::<Vec<_>>();
+    // Int64 bounds are null for IPv6, so row validity follows the networks instead.
+    let present = BooleanChunked::from_iter_options(
+        PlSmallStr::EMPTY,
+        networks.iter().map(|network| network.map(|_| true)),
+    );
+
+    let fields = [
+        address_series(RANGE_START.into(), &starts, kwargs.output)?,
+        address_series(RANGE_END.into(), &ends, kwargs.output)?,
+    ];
+    let chunked = StructChunked::from_series(name, networks.len(), fields.iter())?;
+    Ok(chunked
+        .with_outer_validity(present.rechunk_validity())
+        .into_series())
+}
+
+#[polars_expr(output_type_func=string_list_output)]
+pub fn cidr_from_range(inputs: &[Series], kwargs: FromRangeKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 2,
+        ComputeError: "cidr.from_range expects 2 arguments (start and end expressions or literals)"
+    );
+
+    // Either bound may be a literal broadcast against the other; full-length bounds keep
+    // per-row nulls.
+    let len = inputs[0].len().max(inputs[1].len());
+    let name = inputs[0].name().clone();
+    let bound = |series: &Series, arg_name: &str| {
+        if series.len() == len {
+            parse_bound_address_series(series, kwargs.on_error).map(AddressArgument::Series)
+        } else {
+            resolve_address_argument(series, arg_name, len, kwargs.on_error)
+        }
+    };
+    let (start, end) = (bound(&inputs[0], "start")?, bound(&inputs[1], "end")?);
+
+    let rows = (0..len)
+        .map(|idx| {
+            let (start, end) = (start.value_at(idx)?, end.value_at(idx)?);
+            let version = match (start, end) {
+                (IpAddr::V4(_), IpAddr::V4(_)) => 4,
+                (IpAddr::V6(_), IpAddr::V6(_)) => 6,
+                _ => return None,
+            };
+
+            let range = (address_value(&start), address_value(&end));
+            (range.0 <= range.1).then(|| range_to_networks(range, version))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(network_list_series(name, &rows))
+}
+
+#[polars_expr(output_type=String)]
+pub fn cidr_nth_host(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 2,
+        ComputeError: "cidr.nth_host expects 2 arguments (expression, index expression or literal)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+    let index = resolve_int_argument(&inputs[1], "n", len)?;
+
+    let addresses = networks
+        .iter()
+        .enumerate()
+        .map(|(idx, network)| {
+            let network = network.as_ref()?;
+            nth_host(network, index.value_at(idx)?)
+        })
+        .collect::<Vec<_>>();
+
+    address_series(name, &addresses, AddressOutput::String)
+}
+
+#[polars_expr(output_type=String)]
+pub fn cidr_next(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.next expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .map(|network| network.and_then(|network| adjacent_network(&network, true)))
+        .map(|network| network.map(|network| network.to_string()));
+
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type=String)]
+pub fn cidr_previous(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.previous expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .map(|network| network.and_then(|network| adjacent_network(&network, false)))
+        .map(|network| network.map(|network| network.to_string()));
+
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type_func=string_list_output)]
+pub fn cidr_subnets(inputs: &[Series], kwargs: SubnetsKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 2,
+        ComputeError: "cidr.subnets expects 2 arguments (expression, new prefix expression or literal)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs.parse)?;
+    let new_prefix = resolve_int_argument(&inputs[1], "new_prefix", len)?;
+
+    let mut rows = Vec::with_capacity(len);
+    for (idx, network) in networks.iter().enumerate() {
+        let subnet = network
+            .as_ref()
+            .zip(new_prefix.value_at(idx))
+            .and_then(|(network, new_prefix)| {
+                subnet_prefix(network, new_prefix).map(|new_prefix| (network, new_prefix))
+            });
+
+        let row = match subnet {
+            Some((network, new_prefix)) => {
+                let count = subnet_count(network, new_prefix);
+                polars_ensure!(
+                    count.is_some_and(|count| count <= u128::from(kwargs.max_count)),
+                    ComputeError: "cidr.subnets would split '{}' (row {}) into more than {} /{} networks; raise max_count to allow it",
+                    network,
+                    idx,
+                    kwargs.max_count,
+                    new_prefix
+                );
+                Some(subnets(network, new_prefix))
+            }
+            None => None,
+        };
+        rows.push(row);
+    }
+
+    Ok(network_list_series(name, &rows))
+}
+
+#[polars_expr(output_type=UInt64)]
+pub fn cidr_subnets_count(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 2,
+        ComputeError: "cidr.subnets_count expects 2 arguments (expression, new prefix expression or literal)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+    let new_prefix = resolve_int_argument(&inputs[1], "new_prefix", len)?;
+
+    let values = networks.iter().enumerate().map(|(idx, network)| {
+        let network = network.as_ref()?;
+        let new_prefix = subnet_prefix(network, new_prefix.value_at(idx)?)?;
+        // Counts beyond 2^64 only occur for IPv6 and overflow the output type.
+        subnet_count(network, new_prefix).and_then(|count| u64::try_from(count).ok())
+    });
+
+    let chunked = UInt64Chunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type_func=string_list_output)]
+pub fn cidr_hosts(inputs: &[Series], kwargs: HostsKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.hosts expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+
+    let mut rows = Vec::with_capacity(len);
+    for (idx, network) in parse_network_series(series, &kwargs.parse)?.into_iter().enumerate() {
+        let row = match network {
+            Some(network) => {
+                let (first, last) = host_range(&network);
+                polars_ensure!(
+                    last - first < u128::from(kwargs.limit),
+                    ComputeError: "cidr.hosts of '{}' (row {}) exceeds the limit of {} addresses; raise limit to allow it",
+                    network,
+                    idx,
+                    kwargs.limit
+                );
+
+                let version = network_version(&network);
+                Some(
+                    (first..=last)
+                        .filter_map(|value| address_from_value(value, version))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            None => None,
+        };
+        rows.push(row);
+    }
+
+    Ok(string_list_series(name, &rows))
+}
+
+#[polars_expr(output_type_func_with_kwargs=count_output)]
+pub fn cidr_num_addresses(inputs: &[Series], kwargs: CountKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.num_addresses expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let counts = parse_network_series(series, &kwargs.parse)?
+        .into_iter()
+        .map(|network| network.map(|network| AddressCount::of_range(network_range(&network))))
+        .collect::<Vec<_>>();
+
+    Ok(count_series(name, &counts, kwargs.output))
+}
+
+#[polars_expr(output_type_func_with_kwargs=count_output)]
+pub fn cidr_num_hosts(inputs: &[Series], kwargs: CountKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.num_hosts expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let counts = parse_network_series(series, &kwargs.parse)?
+        .into_iter()
+        .map(|network| network.map(|network| AddressCount::of_range(host_range(&network))))
+        .collect::<Vec<_>>();
+
+    Ok(count_series(name, &counts, kwargs.output))
+}
+
+#[polars_expr(output_type=Int64)]
+pub fn cidr_netmask(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1 || inputs.len() == 2,
+        ComputeError: "cidr.netmask expects 1 or 2 arguments (expression, optional binary flag)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+
+    let binary = if inputs.len() == 2 {
+        resolve_bool_argument(&inputs[1], "binary", len)?
+    } else {
+        BoolArgument::Literal(false)
+    };
+
+    let mut results: Vec<Option<i64>> = Vec::with_capacity(len);
+    for (idx, network) in networks.into_iter().enumerate() {
+        let entry = match (network, binary.value_at(idx)) {
+            (Some(network), Some(is_binary)) => {
+                if is_binary {
+                    match network {
+                        IpNetwork::V4(net) => Some(i64::from(u32::from(net.mask()))),
+                        IpNetwork::V6(_) => None,
+                    }
+                } else {
+                    Some(i64::from(network.prefix()))
+                }
+            }
+            (Some(_), None) => None,
+            _ => None,
+        };
+
+        results.push(entry);
+    }
+
+    let chunked = Int64Chunked::from_iter(results);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type=Int64)]
+pub fn cidr_version(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.version expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+
+    let mut values = Vec::with_capacity(len);
+    for network in parse_network_series(series, &kwargs)? {
+        let entry = network.map(|network| match network {
+            IpNetwork::V4(_) => 4,
+            IpNetwork::V6(_) => 6,
+        });
+        values.push(entry.map(i64::from));
+    }
+
+    let chunked = Int64Chunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type=Boolean)]
+pub fn cidr_is_valid(inputs: &[Series], kwargs: ValidateKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.is_valid expects 1 argument (expression)"
+    );
+
+    let series = inputs[0].str()?;
+    let name = series.name().clone();
+    let values = series
+        .into_iter()
+        .map(|value| value.map(|text| network_error(text, &kwargs).is_none()));
+
+    let chunked = BooleanChunked::from_iter_options(name, values);
+    Ok(chunked.into_series())
+}
+
+#[polars_expr(output_type=String)]
+pub fn cidr_parse_error(inputs: &[Series], kwargs: ValidateKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.parse_error expects 1 argument (expression)"
+    );
+
+    let series = inputs[0].str()?;
+    let name = series.name().clone();
+    let values = series
+        .into_iter()
+        .map(|value| value.and_then(|text| network_error(text, &kwargs)));
+
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type=Boolean)]
+pub fn cidr_has_host_bits(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.has_host_bits expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .map(|network| network.map(|network| network.ip() != network.network()));
+
+    let chunked = BooleanChunked::from_iter_options(name, values);
+    Ok(chunked.into_series())
+}
+
+#[polars_expr(output_type=String)]
+pub fn cidr_normalize(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.normalize expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .map(|network| network.map(|network| canonical_network(&network).to_string()));
+
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type_func_with_kwargs=ip_int_output)]
+pub fn ip_to_int(inputs: &[Series], kwargs: IpToIntKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "ip.to_int expects 1 argument (expression)"
+    );
+    polars_ensure!(
+        !matches!(kwargs.output, AddressOutput::String),
+        ComputeError: "ip.to_int output must be 'int64' or 'struct'"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let addresses = parse_address_series(series, kwargs.allow_prefix)?;
+
+    address_series(name, &addresses, kwargs.output)
+}
+
+#[polars_expr(output_type=String)]
+pub fn ip_from_int(inputs: &[Series], kwargs: FromIntKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "ip.from_int expects 1 argument (expression)"
+    );
+    polars_ensure!(
+        kwargs.version == 4 || kwargs.version == 6,
+        ComputeError: "ip.from_int version must be 4 or 6 (got {})",
+        kwargs.version
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = match series.dtype() {
+        DataType::Struct(_) => joined_address_values(series)?,
+        dtype if dtype.is_integer() => series
+            .cast(&DataType::UInt64)?
+            .u64()?
+            .into_iter()
+            .map(|value| value.map(u128::from))
+            .collect(),
+        dtype => polars_bail!(
+            ComputeError: "ip.from_int expects integers or address structs (got {:?})",
+            dtype
+        ),
+    };
+
+    let addresses = values
+        .into_iter()
+        .map(|value| value.and_then(|value| address_from_value(value, kwargs.version)))
+        .collect::<Vec<_>>();
+
+    address_series(name, &addresses, AddressOutput::String)
+}
+
+#[polars_expr(output_type=String)]
+pub fn ip_add(inputs: &[Series], kwargs: IpKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 2,
+        ComputeError: "ip.add expects 2 arguments (expression, offset expression or literal)"
+    );
+
+    let series = &inputs[0];
+    let len = series.len();
+    let name = series.name().clone();
+    let addresses = parse_address_series(series, kwargs.allow_prefix)?;
+    let offset = resolve_int_argument(&inputs[1], "offset", len)?;
+
+    let addresses = addresses
+        .iter()
+        .enumerate()
+        .map(|(idx, address)| offset_address(address.as_ref()?, offset.value_at(idx)?))
+        .collect::<Vec<_>>();
+
+    address_series(name, &addresses, AddressOutput::String)
+}
+
+#[polars_expr(output_type=Boolean)]
+pub fn ip_is_valid(inputs: &[Series], kwargs: IpKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "ip.is_valid expects 1 argument (expression)"
+    );
+
+    let series = inputs[0].str()?;
+    let name = series.name().clone();
+    let values = series.into_iter().map(|value| {
+        value.map(|text| parse_optional_address(Some(text), kwargs.allow_prefix).is_some())
+    });
+
+    let chunked = BooleanChunked::from_iter_options(name, values);
+    Ok(chunked.into_series())
+}
+
+#[polars_expr(output_type=Int64)]
+pub fn ip_version(inputs: &[Series], kwargs: IpKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "ip.version expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_address_series(series, kwargs.allow_prefix)?
+        .into_iter()
+        .map(|address| {
+            address.map(|address| match address {
+                IpAddr::V4(_) => 4_i64,
+                IpAddr::V6(_) => 6_i64,
+            })
+        });
+
+    let chunked = Int64Chunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type=Boolean)]
+pub fn ip_is_class(inputs: &[Series], kwargs: IpClassKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "ip.is_* expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_address_series(series, kwargs.allow_prefix)?
+        .into_iter()
+        .map(|address| address.map(|address| address_in_class(&address, kwargs.class)));
+
+    let chunked = BooleanChunked::from_iter_options(name, values);
+    Ok(chunked.into_series())
+}
+
+#[polars_expr(output_type=String)]
+pub fn ip_format(inputs: &[Series], kwargs: IpFormatKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "ip.format expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_address_series(series, kwargs.allow_prefix)?
+        .into_iter()
+        .map(|address| address.map(|address| format_address(&address, kwargs.style)));
+
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+#[polars_expr(output_type_func_with_kwargs=supernet_output)]
+pub fn cidr_supernet(inputs: &[Series], kwargs: SupernetKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.supernet expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+
+    match series.dtype() {
+        DataType::String | DataType::Struct(_) => {
+            let networks = parse_network_series(series, &kwargs.parse)?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+
+            match kwargs.mixed_family {
+                MixedFamily::Split => {
+                    let (ipv4, ipv6) = split_families(&networks);
+                    let v4 = minimal_supernet_ipv4(&ipv4).map(|net| net.to_string());
+                    let v6 = minimal_supernet_ipv6(&ipv6).map(|net| net.to_string());
+                    let fields = [
+                        StringChunked::from_iter([v4])
+                            .with_name(SUPERNET_V4.into())
+                            .into_series(),
+                        StringChunked::from_iter([v6])
+                            .with_name(SUPERNET_V6.into())
+                            .into_series(),
+                    ];
+
+                    let chunked = StructChunked::from_series(name, 1, fields.iter())?;
+                    Ok(chunked.into_series())
+                }
+                mixed_family => {
+                    let map_ipv4 = matches!(mixed_family, MixedFamily::Mapped);
+                    let result = minimal_supernet(&networks, map_ipv4).map(|net| net.to_string());
+                    let chunked = StringChunked::from_iter([result]);
+                    Ok(chunked.with_name(name).into_series())
+                }
+            }
+        }
+        dtype => polars_bail!(
+            ComputeError: "cidr.supernet expects UTF-8 or encoded values (got {:?})",
+            dtype
+        ),
+    }
+}
+
+#[polars_expr(output_type_func=string_list_output)]
+pub fn cidr_collapse(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.collapse expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let collapsed = RangeSet::from_networks(&networks).to_networks();
+    Ok(network_list_series(name, &[Some(collapsed)]))
+}
+
+#[polars_expr(output_type_func_with_kwargs=unique_count_output)]
+pub fn cidr_num_unique_addresses(
+    inputs: &[Series],
+    kwargs: UniqueCountKwargs,
+) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.num_unique_addresses expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs.parse)?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let set = RangeSet::from_networks(&networks);
+    let count = match (kwargs.mixed_family, set.family_address_counts()) {
+        (MixedFamily::Split, (v4, v6)) => {
+            let fields = [
+                count_series(SUPERNET_V4.into(), &[v4], kwargs.output),
+                count_series(SUPERNET_V6.into(), &[v6], kwargs.output),
+            ];
+            let chunked = StructChunked::from_series(name, 1, fields.iter())?;
+            return Ok(chunked.into_series());
+        }
+        (MixedFamily::Mapped, _) => Some(set.mapped_address_count()),
+        (MixedFamily::Null, (Some(_), Some(_))) => None,
+        (MixedFamily::Null, (v4, v6)) => Some(v4.or(v6).unwrap_or_default()),
+    };
+
+    Ok(count_series(name, &[count], kwargs.output))
+}
+
+#[polars_expr(output_type_func=string_list_output)]
+pub fn cidr_set_operation(inputs: &[Series], kwargs: SetOperationKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 2,
+        ComputeError: "cidr set operations expect 2 arguments (expression, cidr expression, list or literal)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs.parse)?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let others = resolve_network_set_argument(&inputs[1], "other", &kwargs.parse)?;
+
+    let left = RangeSet::from_networks(&networks);
+    let right = RangeSet::from_networks(&others);
+    let result = match kwargs.operation {
+        SetOperation::Union => left.union(&right),
+        SetOperation::Intersection => left.intersection(&right),
+        SetOperation::Difference => left.difference(&right),
+        SetOperation::SymmetricDifference => left.symmetric_difference(&right),
+    };
+
+    Ok(network_list_series(name, &[Some(result.to_networks())]))
+}
+
+#[polars_expr(output_type_func=encoded_network_output)]
+pub fn cidr_encode(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.encode expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let networks = parse_network_series(series, &kwargs)?;
+
+    encode_network_series(name, &networks)
+}
+
+#[polars_expr(output_type=String)]
+pub fn cidr_decode(inputs: &[Series], kwargs: ParseOptions) -> PolarsResult<Series> {
+    polars_ensure!(
+        inputs.len() == 1,
+        ComputeError: "cidr.decode expects 1 argument (expression)"
+    );
+
+    let series = &inputs[0];
+    let name = series.name().clone();
+    let values = parse_network_series(series, &kwargs)?
+        .into_iter()
+        .map(|network| network.map(|network| network.to_string()));
+
+    let chunked = StringChunked::from_iter(values);
+    Ok(chunked.with_name(name).into_series())
+}
+
+fn address_output(input_fields: &[Field], kwargs: AddressKwargs) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    Ok(Field::new(field.name().clone(), address_dtype(kwargs.output)))
+}
+
+fn range_output(input_fields: &[Field], kwargs: AddressKwargs) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    let dtype = DataType::Struct(vec![
+        Field::new(RANGE_START.into(), address_dtype(kwargs.output)),
+        Field::new(RANGE_END.into(), address_dtype(kwargs.output)),
+    ]);
+    Ok(Field::new(field.name().clone(), dtype))
+}
+
+fn address_dtype(output: AddressOutput) -> DataType {
+    match output {
+        AddressOutput::Int64 => DataType::Int64,
+        AddressOutput::String => DataType::String,
+        AddressOutput::Struct => DataType::Struct(vec![
+            Field::new(ENCODED_ADDR_HI.into(), DataType::UInt64),
+            Field::new(ENCODED_ADDR_LO.into(), DataType::UInt64),
+        ]),
+    }
+}
+
+fn ip_int_output(input_fields: &[Field], kwargs: IpToIntKwargs) -> PolarsResult<Field> {
+    address_output(
+        input_fields,
+        AddressKwargs {
+            output: kwargs.output,
+            parse: ParseOptions::default(),
+        },
+    )
+}
+
+fn supernet_output(input_fields: &[Field], kwargs: SupernetKwargs) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    let dtype = match kwargs.mixed_family {
+        MixedFamily::Split => DataType::Struct(vec![
+            Field::new(SUPERNET_V4.into(), DataType::String),
+            Field::new(SUPERNET_V6.into(), DataType::String),
+        ]),
+        MixedFamily::Null | MixedFamily::Mapped => DataType::String,
+    };
+    Ok(Field::new(field.name().clone(), dtype))
+}
+
+fn overlapping_output(input_fields: &[Field], kwargs: OverlappingKwargs) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    let inner = if kwargs.values {
+        DataType::String
+    } else {
+        DataType::UInt32
+    };
+    Ok(Field::new(field.name().clone(), DataType::List(Box::new(inner))))
+}
+
+fn count_output(input_fields: &[Field], kwargs: CountKwargs) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    Ok(Field::new(field.name().clone(), count_dtype(kwargs.output)))
+}
+
+fn count_dtype(output: CountOutput) -> DataType {
+    match output {
+        CountOutput::UInt64 => DataType::UInt64,
+        CountOutput::Float64 => DataType::Float64,
+        CountOutput::String => DataType::String,
+    }
+}
+
+fn unique_count_output(input_fields: &[Field], kwargs: UniqueCountKwargs) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    let count_dtype = count_dtype(kwargs.output);
+    let dtype = match kwargs.mixed_family {
+        MixedFamily::Split => DataType::Struct(vec![
+            Field::new(SUPERNET_V4.into(), count_dtype.clone()),
+            Field::new(SUPERNET_V6.into(), count_dtype),
+        ]),
+        MixedFamily::Null | MixedFamily::Mapped => count_dtype,
+    };
+    Ok(Field::new(field.name().clone(), dtype))
+}
+
+fn string_list_output(input_fields: &[Field]) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    Ok(Field::new(
+        field.name().clone(),
+        DataType::List(Box::new(DataType::String)),
+    ))
+}
+
+fn encoded_network_output(input_fields: &[Field]) -> PolarsResult<Field> {
+    let field = &input_fields[0];
+    let fields = vec![
+        Field::new(ENCODED_FAMILY.into(), DataType::UInt8),
+        Field::new(ENCODED_ADDR_HI.into(), DataType::UInt64),
+        Field::new(ENCODED_ADDR_LO.into(), DataType::UInt64),
+        Field::new(ENCODED_PREFIX.into(), DataType::UInt8),
+    ];
+    Ok(Field::new(field.name().clone(), DataType::Struct(fields)))
+}
+
+enum NetworkArgument {
+    Literal(IpNetwork),
+    Series(Vec<Option<IpNetwork>>),
+}
+
+impl NetworkArgument {
+    fn value_at(&self, idx: usize) -> Option<&IpNetwork> {
+        match self {
+            NetworkArgument::Literal(network) => Some(network),
+            NetworkArgument::Series(values) => values.get(idx).and_then(|value| value.as_ref()),
+        }
+    }
+}
+
+fn resolve_network_argument(
+    series: &Series,
+    arg_name: &str,
+    expected_len: usize,
+    options: &ParseOptions,
+) -> PolarsResult<NetworkArgument> {
+    if series.len() == 1 {
+        let network = parse_literal_network(series, arg_name, options)?;
+        return Ok(NetworkArgument::Literal(network));
+    }
+
+    polars_ensure!(
+        series.len() == expected_len,
+        ComputeError: "{} argument must be a literal or expression with {} rows (got {})",
+        arg_name,
+        expected_len,
+        series.len()
+    );
+
+    Ok(NetworkArgument::Series(parse_network_series(series, options)?))
+}
+
+enum NetworkListArgument {
+    Literal(Vec<IpNetwork>),
+    Column(Vec<IpNetwork>),
+    Series(Vec<Option<Vec<IpNetwork>>>),
+}
+
+enum IntArgument {
+    Literal(i64),
+    Series(Vec<Option<i64>>),
+}
+
+impl IntArgument {
+    fn value_at(&self, idx: usize) -> Option<i64> {
+        match self {
+            IntArgument::Literal(value) => Some(*value),
+            IntArgument::Series(values) => values.get(idx).copied().flatten(),
+        }
+    }
+}
+
+enum AddressArgument {
+    Literal(IpAddr),
+    Series(Vec<Option<IpAddr>>),
+}
+
+impl AddressArgument {
+    fn value_at(&self, idx: usize) -> Option<IpAddr> {
+        match self {
+            AddressArgument::Literal(value) => Some(*value),
+            AddressArgument::Series(values) => values.get(idx).copied().flatten(),
+        }
+    }
+}
+
+enum BoolArgument {
+    Literal(bool),
+    Series(Vec<Option<bool>>),
+}
+
+impl BoolArgument {
+    fn value_at(&self, idx: usize) -> Option<bool> {
+        match self {
+            BoolArgument::Literal(value) => Some(*value),
+            BoolArgument::Series(values) => values.get(idx).copied().flatten(),
+        }
+    }
+}
+
+impl NetworkListArgument {
+    /// Networks shared by every row, when the argument is a literal or a whole column.
+    fn shared_values(&self) -> Option<&[IpNetwork]> {
+        match self {
+            NetworkListArgument::Literal(values) | NetworkListArgument::Column(values) => {
+                Some(values.as_slice())
+            }
+            NetworkListArgument::Series(_) => None,
+        }
+    }
+
+    fn values_at(&self, idx: usize) -> Option<&[IpNetwork]> {
+        match self {
+            NetworkListArgument::Literal(values) | NetworkListArgument::Column(values) => {
+                Some(values.as_slice())
+            }
+            NetworkListArgument::Series(rows) => rows
                 .get(idx)
                 .and_then(|row| row.as_ref().map(|values| values.as_slice())),
         }
     }
 }
 
-fn parse_optional_network(value: Option<&str>) -> Option<IpNetwork> {
-    value.and_then(|text| text.parse::<IpNetwork>().ok())
+/// Reason `text` is not a valid network, `None` when it is.
+fn network_error(text: &str, kwargs: &ValidateKwargs) -> Option<String> {
+    match parse_network(text, &kwargs.parse.notations) {
+        Ok(network) if !kwargs.allow_host_bits && network.ip() != network.network() => Some(
+            format!("host bits set (network is {})", canonical_network(&network)),
+        ),
+        Ok(_) => None,
+        Err(_) => Some(parse_error_reason(text, &kwargs.parse.notations)),
+    }
+}
+
+/// Parse the value of row `idx` in `column`, applying `options.on_error` to invalid text.
+fn parse_optional_network(
+    value: Option<&str>,
+    column: &str,
+    idx: usize,
+    options: &ParseOptions,
+) -> PolarsResult<Option<IpNetwork>> {
+    let Some(text) = value else {
+        return Ok(None);
+    };
+
+    match parse_network(text, &options.notations) {
+        Ok(network) => Ok(Some(network)),
+        Err(_) if matches!(options.on_error, OnError::Raise) => polars_bail!(
+            ComputeError: "invalid CIDR '{}' in column '{}' at row {}: {}",
+            text,
+            column,
+            idx,
+            parse_error_reason(text, &options.notations)
+        ),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Parse a bare address, rejecting `addr/prefix` notation unless `allow_prefix` is set.
+fn parse_optional_address(value: Option<&str>, allow_prefix: bool) -> Option<IpAddr> {
+    value.and_then(|text| match text.parse::<IpAddr>() {
+        Ok(address) => Some(address),
+        Err(_) if allow_prefix => text.parse::<IpNetwork>().ok().map(|network| network.ip()),
+        Err(_) => None,
+    })
+}
+
+fn parse_address_series(series: &Series, allow_prefix: bool) -> PolarsResult<Vec<Option<IpAddr>>> {
+    let chunked = series.str()?;
+    Ok(chunked
+        .into_iter()
+        .map(|value| parse_optional_address(value, allow_prefix))
+        .collect())
+}
+
+/// Parse a column of bare addresses bounding a range, applying `on_error` to invalid text.
+fn parse_bound_address_series(
+    series: &Series,
+    on_error: OnError,
+) -> PolarsResult<Vec<Option<IpAddr>>> {
+    let column = series.name();
+    series
+        .str()?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| match value.map(|text| (text, text.parse::<IpAddr>())) {
+            Some((_, Ok(address))) => Ok(Some(address)),
+            Some((text, Err(err))) if matches!(on_error, OnError::Raise) => polars_bail!(
+                ComputeError: "invalid IP address '{}' in column '{}' at row {}: {}",
+                text,
+                column,
+                idx,
+                err
+            ),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Parse a column of CIDR strings or `cidr.encode` structs into networks.
+///
+/// Null rows yield `None`, as do unparseable ones unless `options` asks to raise.
+fn parse_network_series(
+    series: &Series,
+    options: &ParseOptions,
+) -> PolarsResult<Vec<Option<IpNetwork>>> {
+    match series.dtype() {
+        DataType::String => series
+            .str()?
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| parse_optional_network(value, series.name(), idx, options))
+            .collect(),
+        DataType::Struct(_) => decode_network_series(series, options),
+        dtype => polars_bail!(
+            ComputeError: "expected CIDR strings or encoded networks (got {:?})",
+            dtype
+        ),
+    }
+}
+
+fn parse_literal_network(
+    series: &Series,
+    arg_name: &str,
+    options: &ParseOptions,
+) -> PolarsResult<IpNetwork> {
+    if let Ok(chunked) = series.str() {
+        let value = chunked
+            .get(0)
+            .ok_or_else(|| polars_err!(ComputeError: "{} argument cannot be null", arg_name))?;
+
+        return parse_network(value, &options.notations).map_err(|_| {
+            let reason = parse_error_reason(value, &options.notations);
+            polars_err!(ComputeError: "invalid {} CIDR '{}': {}", arg_name, value, reason)
+        });
+    }
+
+    parse_network_series(series, options)?
+        .into_iter()
+        .next()
+        .flatten()
+        .ok_or_else(|| polars_err!(ComputeError: "{} argument cannot be null", arg_name))
+}
+
+fn encode_network_series(
+    name: PlSmallStr,
+    networks: &[Option<IpNetwork>],
+) -> PolarsResult<Series> {
+    let len = networks.len();
+    let mut families = Vec::with_capacity(len);
+    let mut highs = Vec::with_capacity(len);
+    let mut lows = Vec::with_capacity(len);
+    let mut prefixes = Vec::with_capacity(len);
+
+    for network in networks {
+        let entry = network.map(|network| {
+            let (family, addr) = match network {
+                IpNetwork::V4(net) => (4_u8, u128::from(u32::from(net.ip()))),
+                IpNetwork::V6(net) => (6_u8, u128::from(net.ip())),
+            };
+            (family, (addr >> 64) as u64, addr as u64, network.prefix())
+        });
+
+        families.push(entry.map(|(family, _, _, _)| family));
+        highs.push(entry.map(|(_, high, _, _)| high));
+        lows.push(entry.map(|(_, _, low, _)| low));
+        prefixes.push(entry.map(|(_, _, _, prefix)| prefix));
+    }
+
+    let families = UInt8Chunked::from_iter(families).with_name(ENCODED_FAMILY.into());
+    let validity = families.rechunk_validity();
+    let fields = [
+        families.into_series(),
+        UInt64Chunked::from_iter(highs)
+            .with_name(ENCODED_ADDR_HI.into())
+            .into_series(),
+        UInt64Chunked::from_iter(lows)
+            .with_name(ENCODED_ADDR_LO.into())
+            .into_series(),
+        UInt8Chunked::from_iter(prefixes)
+            .with_name(ENCODED_PREFIX.into())
+            .into_series(),
+    ];
+
+    let chunked = StructChunked::from_series(name, len, fields.iter())?;
+    Ok(chunked.with_outer_validity(validity).into_series())
+}
+
+fn decode_network_series(
+    series: &Series,
+    options: &ParseOptions,
+) -> PolarsResult<Vec<Option<IpNetwork>>> {
+    let chunked = series.struct_()?;
+    let families = chunked.field_by_name(ENCODED_FAMILY)?;
+    let prefixes = chunked.field_by_name(ENCODED_PREFIX)?;
+    let addresses = joined_address_values(series)?;
+
+    families
+        .u8()?
+        .into_iter()
+        .zip(addresses)
+        .zip(prefixes.u8()?)
+        .enumerate()
+        .map(|(idx, ((family, addr), prefix))| {
+            let (Some(family), Some(addr), Some(prefix)) = (family, addr, prefix) else {
+                return Ok(None);
+            };
+
+            let network = decode_network(family, addr, prefix);
+            polars_ensure!(
+                network.is_some() || !matches!(options.on_error, OnError::Raise),
+                ComputeError: "invalid encoded network (family {}, address {:#x}, prefix {}) in column '{}' at row {}",
+                family,
+                addr,
+                prefix,
+                series.name(),
+                idx
+            );
+            Ok(network)
+        })
+        .collect()
+}
+
+/// Join the `addr_hi`/`addr_lo` fields of a struct column into 128-bit address values.
+fn joined_address_values(series: &Series) -> PolarsResult<Vec<Option<u128>>> {
+    let chunked = series.struct_()?;
+    let highs = chunked.field_by_name(ENCODED_ADDR_HI)?;
+    let lows = chunked.field_by_name(ENCODED_ADDR_LO)?;
+
+    let validity = series.is_not_null();
+    let values = highs
+        .u64()?
+        .into_iter()
+        .zip(lows.u64()?)
+        .zip(&validity)
+        .map(|((high, low), valid)| match (valid, high, low) {
+            (Some(true), Some(high), Some(low)) => Some((u128::from(high) << 64) | u128::from(low)),
+            _ => None,
+        })
+        .collect();
+
+    Ok(values)
+}
+
+fn decode_network(family: u8, addr: u128, prefix: u8) -> Option<IpNetwork> {
+    match family {
+        4 => {
+            let addr = u32::try_from(addr).ok()?;
+            Ipv4Network::new(Ipv4Addr::from(addr), prefix)
+                .ok()
+                .map(IpNetwork::V4)
+        }
+        6 => Ipv6Network::new(Ipv6Addr::from(addr), prefix)
+            .ok()
+            .map(IpNetwork::V6),
+        _ => None,
+    }
 }
 
 fn resolve_network_list_argument(
     series: &Series,
     arg_name: &str,
     expected_len: usize,
+    options: &ParseOptions,
 ) -> PolarsResult<NetworkListArgument> {
     if let Ok(list) = series.list() {
-        return resolve_list_argument(list, arg_name, expected_len);
+        return resolve_list_argument(list, arg_name, expected_len, options);
     }
 
-    if series.str().is_ok() {
-        return resolve_string_argument_as_list(series, arg_name);
+    if matches!(series.dtype(), DataType::String | DataType::Struct(_)) {
+        return resolve_column_argument_as_list(series, arg_name, options);
     }
 
     let dtype = series.dtype();
     polars_bail!(
-        ComputeError: "{} argument must be a literal or expression containing CIDR strings or lists (got {:?})",
+        ComputeError: "{} argument must be a literal or expression containing CIDR strings, encoded networks or lists (got {:?})",
         arg_name,
         dtype
     )
 }
 
+/// Resolve a lookup table given as a single list literal or as a column, keeping row
+/// positions so callers can report indices into the table.
+fn resolve_network_table_argument(
+    series: &Series,
+    arg_name: &str,
+    options: &ParseOptions,
+) -> PolarsResult<Vec<Option<IpNetwork>>> {
+    if let Ok(list) = series.list() {
+        polars_ensure!(
+            list.len() == 1,
+            ComputeError: "{} argument must be a list literal or a column (got {} lists)",
+            arg_name,
+            list.len()
+        );
+
+        let values = list
+            .get_as_series(0)
+            .ok_or_else(|| polars_err!(ComputeError: "{} argument cannot be null", arg_name))?;
+        return parse_network_series(&values, options);
+    }
+
+    parse_network_series(series, options)
+}
+
+/// Resolve an address set given as a column, a literal or list rows, ignoring nulls and
+/// unparseable entries.
+fn resolve_network_set_argument(
+    series: &Series,
+    arg_name: &str,
+    options: &ParseOptions,
+) -> PolarsResult<Vec<IpNetwork>> {
+    if let Ok(list) = series.list() {
+        let mut networks = Vec::new();
+        for values in list.into_iter().flatten() {
+            networks.extend(parse_network_series(&values, options)?.into_iter().flatten());
+        }
+        return Ok(networks);
+    }
+
+    match series.dtype() {
+        DataType::String | DataType::Struct(_) => {
+            Ok(parse_network_series(series, options)?.into_iter().flatten().collect())
+        }
+        dtype => polars_bail!(
+            ComputeError: "{} argument must contain CIDR strings, encoded networks or lists (got {:?})",
+            arg_name,
+            dtype
+        ),
+    }
+}
+
 fn resolve_bool_argument(
     series: &Series,
     arg_name: &str,
@@ -440,26 +1916,110 @@ fn resolve_bool_argument(
     Ok(BoolArgument::Series(chunked.into_iter().collect()))
 }
 
-fn minimal_supernet(networks: &[IpNetwork]) -> Option<IpNetwork> {
+fn resolve_address_argument(
+    series: &Series,
+    arg_name: &str,
+    expected_len: usize,
+    on_error: OnError,
+) -> PolarsResult<AddressArgument> {
+    polars_ensure!(
+        series.dtype() == &DataType::String,
+        ComputeError: "{} argument must be a literal or expression containing IP address strings (got {:?})",
+        arg_name,
+        series.dtype()
+    );
+
+    let addresses = parse_bound_address_series(series, on_error)?;
+
+    if addresses.len() == 1 {
+        let value = addresses[0].ok_or_else(|| {
+            polars_err!(ComputeError: "{} argument must be a valid IP address", arg_name)
+        })?;
+
+        return Ok(AddressArgument::Literal(value));
+    }
+
+    polars_ensure!(
+        addresses.len() == expected_len,
+        ComputeError: "{} argument must be a literal or expression with {} rows (got {})",
+        arg_name,
+        expected_len,
+        addresses.len()
+    );
+
+    Ok(AddressArgument::Series(addresses))
+}
+
+fn resolve_int_argument(
+    series: &Series,
+    arg_name: &str,
+    expected_len: usize,
+) -> PolarsResult<IntArgument> {
+    polars_ensure!(
+        series.dtype().is_integer(),
+        ComputeError: "{} argument must be a literal or expression containing integers (got {:?})",
+        arg_name,
+        series.dtype()
+    );
+
+    let values = series.cast(&DataType::Int64)?;
+    let chunked = values.i64()?;
+
+    if chunked.len() == 1 {
+        let value = chunked.get(0).ok_or_else(|| {
+            polars_err!(ComputeError: "{} argument cannot be null", arg_name)
+        })?;
+
+        return Ok(IntArgument::Literal(value));
+    }
+
+    polars_ensure!(
+        chunked.len() == expected_len,
+        ComputeError: "{} argument must be a literal or expression with {} rows (got {})",
+        arg_name,
+        expected_len,
+        chunked.len()
+    );
+
+    Ok(IntArgument::Series(chunked.into_iter().collect()))
+}
+
+fn minimal_supernet(networks: &[IpNetwork], map_ipv4: bool) -> Option<IpNetwork> {
     if networks.is_empty() {
         return None;
     }
 
+    let (ipv4, mut ipv6) = split_families(networks);
+
+    match (ipv4.is_empty(), ipv6.is_empty()) {
+        (false, true) => minimal_supernet_ipv4(&ipv4).map(IpNetwork::V4),
+        (true, false) => minimal_supernet_ipv6(&ipv6).map(IpNetwork::V6),
+        (false, false) if map_ipv4 => {
+            ipv6.extend(ipv4.iter().map(ipv4_mapped_network));
+            minimal_supernet_ipv6(&ipv6).map(IpNetwork::V6)
+        }
+        _ => None,
+    }
+}
+
+fn split_families(networks: &[IpNetwork]) -> (Vec<Ipv4Network>, Vec<Ipv6Network>) {
     let mut ipv4 = Vec::new();
     let mut ipv6 = Vec::new();
 
     for network in networks {
         match network {
-            IpNetwork::V4(v4) => ipv4.push(v4.clone()),
-            IpNetwork::V6(v6) => ipv6.push(v6.clone()),
+            IpNetwork::V4(v4) => ipv4.push(*v4),
+            IpNetwork::V6(v6) => ipv6.push(*v6),
         }
     }
 
-    match (ipv4.is_empty(), ipv6.is_empty()) {
-        (false, true) => minimal_supernet_ipv4(&ipv4).map(IpNetwork::V4),
-        (true, false) => minimal_supernet_ipv6(&ipv6).map(IpNetwork::V6),
-        _ => None,
-    }
+    (ipv4, ipv6)
+}
+
+/// Embed an IPv4 network in the IPv4-mapped IPv6 range `::ffff:0:0/96`.
+fn ipv4_mapped_network(network: &Ipv4Network) -> Ipv6Network {
+    Ipv6Network::new(network.network().to_ipv6_mapped(), network.prefix() + 96)
+        .expect("mapped prefix is at most 128")
 }
 
 fn minimal_supernet_ipv4(networks: &[Ipv4Network]) -> Option<Ipv4Network> {
@@ -514,6 +2074,7 @@ fn resolve_list_argument(
     list: &ListChunked,
     arg_name: &str,
     expected_len: usize,
+    options: &ParseOptions,
 ) -> PolarsResult<NetworkListArgument> {
     let len = list.len();
 
@@ -522,7 +2083,7 @@ fn resolve_list_argument(
             .get_as_series(0)
             .ok_or_else(|| polars_err!(ComputeError: "{} argument cannot be null", arg_name))?;
 
-        let networks = parse_literal_network_list(&value_series, arg_name)?;
+        let networks = parse_literal_network_list(&value_series, arg_name, options)?;
         return Ok(NetworkListArgument::Literal(networks));
     }
 
@@ -535,9 +2096,9 @@ fn resolve_list_argument(
     );
 
     let mut rows = Vec::with_capacity(len);
-    for row_series in list.clone().into_iter() {
+    for (idx, row_series) in list.clone().into_iter().enumerate() {
         match row_series {
-            Some(inner) => rows.push(parse_expression_network_list(inner)),
+            Some(inner) => rows.push(parse_expression_network_list(inner, idx, options)?),
             None => rows.push(None),
         }
     }
@@ -545,66 +2106,232 @@ fn resolve_list_argument(
     Ok(NetworkListArgument::Series(rows))
 }
 
-fn resolve_string_argument_as_list(
+fn resolve_column_argument_as_list(
     series: &Series,
     arg_name: &str,
+    options: &ParseOptions,
 ) -> PolarsResult<NetworkListArgument> {
-    let chunked = series.str()?;
-
-    if chunked.len() == 1 {
-        let value = chunked
-            .get(0)
-            .ok_or_else(|| polars_err!(ComputeError: "{} argument cannot be null", arg_name))?;
-
-        let network = value.parse::<IpNetwork>().map_err(
-            |err| polars_err!(ComputeError: "invalid {} CIDR '{}': {}", arg_name, value, err),
-        )?;
-
+    if series.len() == 1 {
+        let network = parse_literal_network(series, arg_name, options)?;
         return Ok(NetworkListArgument::Literal(vec![network]));
     }
 
-    let networks = chunked
+    let networks = parse_network_series(series, options)?
         .into_iter()
-        .filter_map(parse_optional_network)
+        .flatten()
         .collect::<Vec<_>>();
 
     Ok(NetworkListArgument::Column(networks))
 }
 
-fn parse_literal_network_list(series: &Series, arg_name: &str) -> PolarsResult<Vec<IpNetwork>> {
-    let chunked = series.str()?;
-    let mut networks = Vec::with_capacity(chunked.len());
+fn parse_literal_network_list(
+    series: &Series,
+    arg_name: &str,
+    options: &ParseOptions,
+) -> PolarsResult<Vec<IpNetwork>> {
+    if let Ok(chunked) = series.str() {
+        let mut networks = Vec::with_capacity(chunked.len());
+
+        for value in chunked.into_iter() {
+            let text = value.ok_or_else(
+                || polars_err!(ComputeError: "{} list argument cannot contain null values", arg_name),
+            )?;
+
+            let network = parse_network(text, &options.notations).map_err(|_| {
+                let reason = parse_error_reason(text, &options.notations);
+                polars_err!(ComputeError: "invalid {} CIDR '{}': {}", arg_name, text, reason)
+            })?;
+
+            networks.push(network);
+        }
+
+        return Ok(networks);
+    }
+
+    parse_network_series(series, options)?
+        .into_iter()
+        .map(|network| {
+            network.ok_or_else(
+                || polars_err!(ComputeError: "{} list argument cannot contain null values", arg_name),
+            )
+        })
+        .collect()
+}
 
-    for value in chunked.into_iter() {
-        let text = value.ok_or_else(
-            || polars_err!(ComputeError: "{} list argument cannot contain null values", arg_name),
-        )?;
+/// Parse the list in row `idx` of a list argument, applying `options.on_error` to invalid entries.
+fn parse_expression_network_list(
+    series: Series,
+    idx: usize,
+    options: &ParseOptions,
+) -> PolarsResult<Option<Vec<IpNetwork>>> {
+    if let Ok(chunked) = series.str() {
+        let mut networks = Vec::with_capacity(chunked.len());
+
+        for text in chunked.into_iter().flatten() {
+            match parse_network(text, &options.notations) {
+                Ok(network) => networks.push(network),
+                Err(_) => match options.on_error {
+                    OnError::Null => return Ok(None),
+                    OnError::Skip => continue,
+                    OnError::Raise => polars_bail!(
+                        ComputeError: "invalid CIDR '{}' in list at row {}: {}",
+                        text,
+                        idx,
+                        parse_error_reason(text, &options.notations)
+                    ),
+                },
+            }
+        }
 
-        let network = text.parse::<IpNetwork>().map_err(
-            |err| polars_err!(ComputeError: "invalid {} CIDR '{}': {}", arg_name, text, err),
-        )?;
+        return Ok(Some(networks));
+    }
 
-        networks.push(network);
+    match parse_network_series(&series, options) {
+        Ok(networks) => Ok(Some(networks.into_iter().flatten().collect())),
+        Err(err) if matches!(options.on_error, OnError::Raise) => Err(err),
+        Err(_) => Ok(None),
     }
+}
 
-    Ok(networks)
+/// Rows sharing a network and prefix length, as visited by `sweep_nested_networks`.
+struct NestedGroup {
+    network: IpNetwork,
+    rows: Vec<usize>,
 }
 
-fn parse_expression_network_list(series: Series) -> Option<Vec<IpNetwork>> {
-    let chunked = series.str().ok()?;
-    let mut networks = Vec::with_capacity(chunked.len());
+/// Visit the non-null rows grouped by (family, network address, prefix) in sorted order,
+/// together with the chain of groups whose networks strictly contain the visited one.
+///
+/// Sorting puts every container before the networks it holds, and CIDR blocks either nest or
+/// are disjoint, so a single sweep keeping the enclosing groups on a stack finds all ancestors.
+fn sweep_nested_networks(
+    networks: &[Option<IpNetwork>],
+    mut visit: impl FnMut(&NestedGroup, &[NestedGroup]),
+) {
+    let mut keyed = networks
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, network)| network.as_ref().map(|net| (network_sort_key(net), idx)))
+        .collect::<Vec<_>>();
+    keyed.sort_unstable();
 
-    for value in chunked.into_iter() {
-        match value {
-            Some(text) => match text.parse::<IpNetwork>() {
-                Ok(network) => networks.push(network),
-                Err(_) => return None,
-            },
-            None => continue,
+    let mut ancestors: Vec<NestedGroup> = Vec::new();
+    for rows in keyed.chunk_by(|(left, _), (right, _)| left == right) {
+        let group = NestedGroup {
+            network: networks[rows[0].1].expect("sorted rows are non-null"),
+            rows: rows.iter().map(|(_, idx)| *idx).collect(),
+        };
+
+        while let Some(ancestor) = ancestors.last() {
+            if network_contains(&ancestor.network, &group.network) {
+                break;
+            }
+            ancestors.pop();
+        }
+
+        visit(&group, &ancestors);
+        ancestors.push(group);
+    }
+}
+
+/// Flag networks contained in a different network of the same slice.
+///
+/// Identical duplicates do not count as parents of each other, but entries sharing a network
+/// and prefix with different host bits do, matching `IpNetwork` inequality.
+fn networks_with_parent(networks: &[Option<IpNetwork>]) -> Vec<bool> {
+    let mut has_parent = vec![false; networks.len()];
+
+    sweep_nested_networks(networks, |group, ancestors| {
+        if !ancestors.is_empty() || has_mixed_host_bits(networks, group) {
+            for idx in &group.rows {
+                has_parent[*idx] = true;
+            }
         }
+    });
+
+    has_parent
+}
+
+/// Most specific network of a different group strictly containing each row.
+///
+/// Unlike `networks_with_parent`, rows sharing a network with different host bits are treated
+/// as duplicates: the parent is always the value of another row, so it can be joined back.
+fn network_parents(networks: &[Option<IpNetwork>]) -> Vec<Option<IpNetwork>> {
+    let mut parents = vec![None; networks.len()];
+    sweep_nested_networks(networks, |group, ancestors| {
+        if let Some(parent) = ancestors.last() {
+            for idx in &group.rows {
+                parents[*idx] = Some(parent.network);
+            }
+        }
+    });
+
+    parents
+}
+
+/// Number of distinct networks strictly containing each row, grouped as in `network_parents`.
+fn network_depths(networks: &[Option<IpNetwork>]) -> Vec<Option<usize>> {
+    let mut depths = vec![None; networks.len()];
+    sweep_nested_networks(networks, |group, ancestors| {
+        for idx in &group.rows {
+            depths[*idx] = Some(ancestors.len());
+        }
+    });
+
+    depths
+}
+
+/// Whether the rows of `group` share a network but not the same host bits.
+fn has_mixed_host_bits(networks: &[Option<IpNetwork>], group: &NestedGroup) -> bool {
+    let first = networks[group.rows[0]];
+    group.rows.iter().any(|idx| networks[*idx] != first)
+}
+
+/// Indices of the other rows overlapping each row, in ascending order.
+fn overlapping_rows(networks: &[Option<IpNetwork>]) -> Vec<Vec<usize>> {
+    let mut overlaps = vec![Vec::new(); networks.len()];
+
+    sweep_nested_networks(networks, |group, ancestors| {
+        for &row in &group.rows {
+            for ancestor in ancestors {
+                for &ancestor_row in &ancestor.rows {
+                    overlaps[row].push(ancestor_row);
+                    overlaps[ancestor_row].push(row);
+                }
+            }
+            overlaps[row].extend(group.rows.iter().filter(|other| **other != row));
+        }
+    });
+
+    for rows in &mut overlaps {
+        rows.sort_unstable();
+    }
+    overlaps
+}
+
+/// Sort key ordering networks by family, network address and prefix length.
+fn network_sort_key(network: &IpNetwork) -> (u8, u128, u8) {
+    match network {
+        IpNetwork::V4(net) => (4, u128::from(u32::from(net.network())), net.prefix()),
+        IpNetwork::V6(net) => (6, u128::from(net.network()), net.prefix()),
     }
+}
 
-    Some(networks)
+/// CIDR blocks either nest or are disjoint, so two networks overlap when one contains the other.
+fn networks_overlap(left: &IpNetwork, right: &IpNetwork) -> bool {
+    network_contains(left, right) || network_contains(right, left)
+}
+
+/// Network with host bits cleared.
+fn canonical_network(network: &IpNetwork) -> IpNetwork {
+    match network {
+        IpNetwork::V4(net) => IpNetwork::V4(
+            Ipv4Network::new(net.network(), net.prefix()).expect("prefix is unchanged"),
+        ),
+        IpNetwork::V6(net) => IpNetwork::V6(
+            Ipv6Network::new(net.network(), net.prefix()).expect("prefix is unchanged"),
+        ),
+    }
 }
 
 fn network_contains(supernet: &IpNetwork, subnet: &IpNetwork) -> bool {
@@ -615,19 +2342,378 @@ fn network_contains(supernet: &IpNetwork, subnet: &IpNetwork) -> bool {
     }
 }
 
-fn network_address_numeric(network: &IpNetwork) -> Option<i64> {
+fn network_address(network: &IpNetwork) -> IpAddr {
     match network {
-        IpNetwork::V4(net) => Some(i64::from(u32::from(net.network()))),
-        // IPv6 values overflow 64-bit integers, so expose them as nulls.
-        IpNetwork::V6(_) => None,
+        IpNetwork::V4(net) => IpAddr::V4(net.network()),
+        IpNetwork::V6(net) => IpAddr::V6(net.network()),
+    }
+}
+
+fn broadcast_address(network: &IpNetwork) -> IpAddr {
+    match network {
+        IpNetwork::V4(net) => IpAddr::V4(net.broadcast()),
+        IpNetwork::V6(net) => IpAddr::V6(ipv6_broadcast_address(net)),
+    }
+}
+
+/// First usable host: the network address itself for point-to-point IPv4 /31 (RFC 3021),
+/// IPv6 /127 (RFC 6164) and single-address networks, the next address otherwise.
+fn first_host(network: &IpNetwork) -> IpAddr {
+    match network {
+        IpNetwork::V4(net) => {
+            let addr = u32::from(net.network());
+            let host = if net.prefix() >= 31 { addr } else { addr + 1 };
+            IpAddr::V4(Ipv4Addr::from(host))
+        }
+        IpNetwork::V6(net) => {
+            // The all-zeros address is the Subnet-Router anycast address.
+            let addr = u128::from(net.network());
+            let host = if net.prefix() >= 127 { addr } else { addr + 1 };
+            IpAddr::V6(Ipv6Addr::from(host))
+        }
+    }
+}
+
+/// Last usable host: the broadcast address is reserved for IPv4 networks larger than /31,
+/// while IPv6 has no broadcast and uses the whole range.
+fn last_host(network: &IpNetwork) -> IpAddr {
+    match network {
+        IpNetwork::V4(net) => {
+            let addr = u32::from(net.broadcast());
+            let host = if net.prefix() >= 31 { addr } else { addr - 1 };
+            IpAddr::V4(Ipv4Addr::from(host))
+        }
+        IpNetwork::V6(net) => IpAddr::V6(ipv6_broadcast_address(net)),
+    }
+}
+
+/// Inclusive range of usable host address values.
+fn host_range(network: &IpNetwork) -> (u128, u128) {
+    (
+        address_value(&first_host(network)),
+        address_value(&last_host(network)),
+    )
+}
+
+/// Address `n` positions from the network address, or from one past the broadcast address
+/// when negative, as Python's `ipaddress` indexes networks. `None` unless it is a usable host.
+fn nth_host(network: &IpNetwork, n: i64) -> Option<IpAddr> {
+    let (start, end) = network_range(network);
+    let value = if n >= 0 {
+        start.checked_add(n as u128)?
+    } else {
+        end.checked_sub(u128::from((n + 1).unsigned_abs()))?
+    };
+
+    let (first, last) = host_range(network);
+    if !(first..=last).contains(&value) {
+        return None;
     }
+    address_from_value(value, network_version(network))
+}
+
+/// Network of the same size right after (`forward`) or before `network`, `None` past the
+/// edges of the address space.
+fn adjacent_network(network: &IpNetwork, forward: bool) -> Option<IpNetwork> {
+    let (start, end) = network_range(network);
+    let value = if forward {
+        end.checked_add(1)?
+    } else {
+        start.checked_sub(end - start)?.checked_sub(1)?
+    };
+
+    let address = address_from_value(value, network_version(network))?;
+    IpNetwork::new(address, network.prefix()).ok()
+}
+
+/// `address` moved by `offset`, `None` when it leaves the address space of its family.
+fn offset_address(address: &IpAddr, offset: i64) -> Option<IpAddr> {
+    let value = address_value(address);
+    let value = if offset >= 0 {
+        value.checked_add(offset as u128)?
+    } else {
+        value.checked_sub(u128::from(offset.unsigned_abs()))?
+    };
+
+    let version = match address {
+        IpAddr::V4(_) => 4,
+        IpAddr::V6(_) => 6,
+    };
+    address_from_value(value, version)
 }
 
-fn broadcast_address_numeric(network: &IpNetwork) -> Option<i64> {
+fn network_version(network: &IpNetwork) -> u8 {
     match network {
-        IpNetwork::V4(net) => Some(i64::from(u32::from(net.broadcast()))),
+        IpNetwork::V4(_) => 4,
+        IpNetwork::V6(_) => 6,
+    }
+}
+
+fn address_numeric(address: &IpAddr) -> Option<i64> {
+    match address {
+        IpAddr::V4(addr) => Some(i64::from(u32::from(*addr))),
         // IPv6 values overflow 64-bit integers, so expose them as nulls.
-        IpNetwork::V6(_) => None,
+        IpAddr::V6(_) => None,
+    }
+}
+
+fn address_value(address: &IpAddr) -> u128 {
+    match address {
+        IpAddr::V4(addr) => u128::from(u32::from(*addr)),
+        IpAddr::V6(addr) => u128::from(*addr),
+    }
+}
+
+fn address_in_class(address: &IpAddr, class: AddressClass) -> bool {
+    match (address, class) {
+        (IpAddr::V4(addr), AddressClass::Private) => addr.is_private(),
+        (IpAddr::V6(addr), AddressClass::Private) => addr.is_unique_local(),
+        (IpAddr::V4(addr), AddressClass::LinkLocal) => addr.is_link_local(),
+        (IpAddr::V6(addr), AddressClass::LinkLocal) => addr.is_unicast_link_local(),
+        (IpAddr::V4(addr), AddressClass::Documentation) => addr.is_documentation(),
+        (IpAddr::V6(addr), AddressClass::Documentation) => {
+            u128::from(*addr) & ipv6_prefix_mask(32) == 0x2001_0db8_u128 << 96
+        }
+        (_, AddressClass::Loopback) => address.is_loopback(),
+        (_, AddressClass::Multicast) => address.is_multicast(),
+        (_, AddressClass::Unspecified) => address.is_unspecified(),
+        (_, AddressClass::Global) => is_global_unicast(address),
+    }
+}
+
+/// IPv4 ranges of the IANA special-purpose registry that are not globally reachable.
+const IPV4_NON_GLOBAL: [(u32, u8); 15] = [
+    (0x0000_0000, 8),  // 0.0.0.0/8 "this network"
+    (0x0a00_0000, 8),  // 10.0.0.0/8 private
+    (0x6440_0000, 10), // 100.64.0.0/10 shared address space
+    (0x7f00_0000, 8),  // 127.0.0.0/8 loopback
+    (0xa9fe_0000, 16), // 169.254.0.0/16 link-local
+    (0xac10_0000, 12), // 172.16.0.0/12 private
+    (0xc000_0000, 24), // 192.0.0.0/24 IETF protocol assignments
+    (0xc000_0200, 24), // 192.0.2.0/24 documentation
+    (0xc0a8_0000, 16), // 192.168.0.0/16 private
+    (0xc612_0000, 15), // 198.18.0.0/15 benchmarking
+    (0xc633_6400, 24), // 198.51.100.0/24 documentation
+    (0xcb00_7100, 24), // 203.0.113.0/24 documentation
+    (0xe000_0000, 4),  // 224.0.0.0/4 multicast
+    (0xf000_0000, 4),  // 240.0.0.0/4 reserved
+    (0xffff_ffff, 32), // 255.255.255.255/32 limited broadcast
+];
+
+/// IPv4 addresses carved out of `IPV4_NON_GLOBAL` that the registry marks globally reachable.
+const IPV4_GLOBAL_EXCEPTIONS: [(u32, u8); 2] = [
+    (0xc000_0009, 32), // 192.0.0.9/32 port control protocol anycast
+    (0xc000_000a, 32), // 192.0.0.10/32 traversal using relays around NAT anycast
+];
+
+/// IPv6 ranges of the IANA special-purpose registry that are not globally reachable.
+const IPV6_NON_GLOBAL: [(u128, u8); 12] = [
+    (0, 128),                          // ::/128 unspecified
+    (1, 128),                          // ::1/128 loopback
+    (0xffff_u128 << 32, 96),           // ::ffff:0:0/96 IPv4-mapped
+    (0x0064_ff9b_0001_u128 << 80, 48), // 64:ff9b:1::/48 local-use translation
+    (0x0100_u128 << 112, 64),          // 100::/64 discard-only
+    (0x2001_u128 << 112, 23),          // 2001::/23 IETF protocol assignments
+    (0x2001_0db8_u128 << 96, 32),      // 2001:db8::/32 documentation
+    (0x2002_u128 << 112, 16),          // 2002::/16 6to4
+    (0x3fff_u128 << 112, 20),          // 3fff::/20 documentation
+    (0xfc00_u128 << 112, 7),           // fc00::/7 unique local
+    (0xfe80_u128 << 112, 10),          // fe80::/10 link-local
+    (0xff00_u128 << 112, 8),           // ff00::/8 multicast
+];
+
+/// IPv6 blocks inside `2001::/23` that the registry marks globally reachable.
+const IPV6_GLOBAL_EXCEPTIONS: [(u128, u8); 6] = [
+    ((0x2001_0001_u128 << 96) | 1, 128), // 2001:1::1/128 port control protocol anycast
+    ((0x2001_0001_u128 << 96) | 2, 128), // 2001:1::2/128 traversal using relays around NAT anycast
+    (0x2001_0003_u128 << 96, 32),         // 2001:3::/32 AMT
+    (0x2001_0004_0112_u128 << 80, 48),    // 2001:4:112::/48 AS112-v6
+    (0x2001_0020_u128 << 96, 28),         // 2001:20::/28 ORCHIDv2
+    (0x2001_0030_u128 << 96, 28),         // 2001:30::/28 drone remote ID
+];
+
+/// Globally reachable unicast address, following the IANA IPv4 and IPv6 special-purpose
+/// address registries like Python's `ipaddress` `is_global`, but also excluding multicast.
+fn is_global_unicast(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(addr) => {
+            let value = u32::from(*addr);
+            let in_range =
+                |&(network, prefix): &(u32, u8)| value & ipv4_prefix_mask(prefix) == network;
+            !IPV4_NON_GLOBAL.iter().any(in_range) || IPV4_GLOBAL_EXCEPTIONS.iter().any(in_range)
+        }
+        IpAddr::V6(addr) => {
+            let value = u128::from(*addr);
+            let in_range =
+                |&(network, prefix): &(u128, u8)| value & ipv6_prefix_mask(prefix) == network;
+            !IPV6_NON_GLOBAL.iter().any(in_range) || IPV6_GLOBAL_EXCEPTIONS.iter().any(in_range)
+        }
+    }
+}
+
+fn format_address(address: &IpAddr, style: AddressStyle) -> String {
+    match (address, style) {
+        (_, AddressStyle::Compressed) => address.to_string(),
+        (IpAddr::V4(addr), AddressStyle::Exploded) => addr.to_string(),
+        (IpAddr::V6(addr), AddressStyle::Exploded) => addr
+            .segments()
+            .iter()
+            .map(|segment| format!("{segment:04x}"))
+            .collect::<Vec<_>>()
+            .join(":"),
+        (IpAddr::V4(addr), AddressStyle::ReversePointer) => {
+            let octets = addr.octets();
+            format!(
+                "{}.{}.{}.{}.in-addr.arpa",
+                octets[3], octets[2], octets[1], octets[0]
+            )
+        }
+        (IpAddr::V6(addr), AddressStyle::ReversePointer) => {
+            let value = u128::from(*addr);
+            let nibbles = (0..32)
+                .map(|idx| format!("{:x}", (value >> (idx * 4)) & 0xf))
+                .collect::<Vec<_>>()
+                .join(".");
+            format!("{nibbles}.ip6.arpa")
+        }
+    }
+}
+
+fn address_from_value(value: u128, version: u8) -> Option<IpAddr> {
+    match version {
+        4 => u32::try_from(value)
+            .ok()
+            .map(|value| IpAddr::V4(Ipv4Addr::from(value))),
+        _ => Some(IpAddr::V6(Ipv6Addr::from(value))),
+    }
+}
+
+fn address_series(
+    name: PlSmallStr,
+    addresses: &[Option<IpAddr>],
+    output: AddressOutput,
+) -> PolarsResult<Series> {
+    match output {
+        AddressOutput::Int64 => {
+            let values = addresses
+                .iter()
+                .map(|address| address.as_ref().and_then(address_numeric));
+            Ok(Int64Chunked::from_iter(values).with_name(name).into_series())
+        }
+        AddressOutput::String => {
+            let values = addresses
+                .iter()
+                .map(|address| address.map(|address| address.to_string()));
+            Ok(StringChunked::from_iter(values).with_name(name).into_series())
+        }
+        AddressOutput::Struct => {
+            let values = addresses
+                .iter()
+                .map(|address| address.as_ref().map(address_value))
+                .collect::<Vec<_>>();
+            split_address_series(name, &values)
+        }
+    }
+}
+
+fn count_series(name: PlSmallStr, counts: &[Option<AddressCount>], output: CountOutput) -> Series {
+    match output {
+        CountOutput::UInt64 => {
+            let values = counts.iter().map(|count| count.and_then(AddressCount::to_u64));
+            UInt64Chunked::from_iter(values).with_name(name).into_series()
+        }
+        CountOutput::Float64 => {
+            let values = counts.iter().map(|count| count.map(AddressCount::to_f64));
+            Float64Chunked::from_iter(values).with_name(name).into_series()
+        }
+        CountOutput::String => {
+            let values = counts.iter().map(|count| count.map(|count| count.to_string()));
+            StringChunked::from_iter(values).with_name(name).into_series()
+        }
+    }
+}
+
+fn network_list_series(name: PlSmallStr, rows: &[Option<Vec<IpNetwork>>]) -> Series {
+    string_list_series(name, rows)
+}
+
+fn string_list_series<T: ToString>(name: PlSmallStr, rows: &[Option<Vec<T>>]) -> Series {
+    let mut builder = ListStringChunkedBuilder::new(name, rows.len(), rows.len());
+    for row in rows {
+        match row {
+            Some(items) => {
+                let values = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+                builder.append_values_iter(values.iter().map(String::as_str));
+            }
+            None => builder.append_null(),
+        }
+    }
+
+    builder.finish().into_series()
+}
+
+/// Build a `{addr_hi, addr_lo}` struct column from 128-bit address values.
+fn split_address_series(name: PlSmallStr, values: &[Option<u128>]) -> PolarsResult<Series> {
+    let highs = UInt64Chunked::from_iter(values.iter().map(|value| value.map(|v| (v >> 64) as u64)))
+        .with_name(ENCODED_ADDR_HI.into());
+    let lows = UInt64Chunked::from_iter(values.iter().map(|value| value.map(|v| v as u64)))
+        .with_name(ENCODED_ADDR_LO.into());
+    let validity = highs.rechunk_validity();
+
+    let fields = [highs.into_series(), lows.into_series()];
+    let chunked = StructChunked::from_series(name, values.len(), fields.iter())?;
+    Ok(chunked.with_outer_validity(validity).into_series())
+}
+
+/// Validate a subnet prefix length, which must lie between the network's prefix and the
+/// address width.
+fn subnet_prefix(network: &IpNetwork, new_prefix: i64) -> Option<u8> {
+    let width = match network {
+        IpNetwork::V4(_) => 32,
+        IpNetwork::V6(_) => 128,
+    };
+
+    u8::try_from(new_prefix)
+        .ok()
+        .filter(|new_prefix| (network.prefix()..=width).contains(new_prefix))
+}
+
+/// Number of `/new_prefix` subnets in `network`, `None` when it reaches 2^128.
+fn subnet_count(network: &IpNetwork, new_prefix: u8) -> Option<u128> {
+    1_u128.checked_shl(u32::from(new_prefix - network.prefix()))
+}
+
+fn subnets(network: &IpNetwork, new_prefix: u8) -> Vec<IpNetwork> {
+    let count = subnet_count(network, new_prefix).unwrap_or(u128::MAX);
+
+    match network {
+        IpNetwork::V4(net) => {
+            let base = u32::from(net.network());
+            let step = (!ipv4_prefix_mask(new_prefix)).wrapping_add(1);
+            // A /0 split into /32s holds 2^32 subnets, so count in u128 and narrow each index.
+            (0..count)
+                .map(|idx| {
+                    let addr = base.wrapping_add((idx as u32).wrapping_mul(step));
+                    IpNetwork::V4(
+                        Ipv4Network::new(Ipv4Addr::from(addr), new_prefix)
+                            .expect("prefix is within the IPv4 width"),
+                    )
+                })
+                .collect()
+        }
+        IpNetwork::V6(net) => {
+            let base = u128::from(net.network());
+            let step = (!ipv6_prefix_mask(new_prefix)).wrapping_add(1);
+            (0..count)
+                .map(|idx| {
+                    let addr = base.wrapping_add(idx.wrapping_mul(step));
+                    IpNetwork::V6(
+                        Ipv6Network::new(Ipv6Addr::from(addr), new_prefix)
+                            .expect("prefix is within the IPv6 width"),
+                    )
+                })
+                .collect()
+        }
     }
 }
 
@@ -671,3 +2757,224 @@ fn ipv6_broadcast_address(network: &Ipv6Network) -> Ipv6Addr {
     Ipv6Addr::from(u128::from(network.network()) | host_mask)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nets(texts: &[Option<&str>]) -> Vec<Option<IpNetwork>> {
+        texts
+            .iter()
+            .map(|text| text.map(|text| text.parse().unwrap()))
+            .collect()
+    }
+
+    fn texts(networks: &[Option<IpNetwork>]) -> Vec<Option<String>> {
+        networks
+            .iter()
+            .map(|network| network.map(|network| network.to_string()))
+            .collect()
+    }
+
+    /// Columns mixing identical duplicates, host bit variants, nulls, both families and nested
+    /// chains, shared by the tests comparing the sweep with a pairwise scan.
+    fn fixtures() -> Vec<Vec<Option<IpNetwork>>> {
+        vec![
+            nets(&[
+                Some("10.0.0.0/8"),
+                None,
+                Some("10.0.0.0/8"),
+                Some("10.1.0.0/16"),
+                Some("10.1.2.0/24"),
+                Some("10.1.2.3/24"),
+                Some("10.1.2.4/24"),
+                Some("10.1.2.3/32"),
+                Some("192.168.0.0/16"),
+                None,
+                Some("2001:db8::/32"),
+                Some("2001:db8::/32"),
+                Some("2001:db8::1/64"),
+                Some("2001:db8::2/64"),
+                Some("2001:db8:1::/48"),
+                Some("::ffff:10.0.0.0/104"),
+            ]),
+            nets(&[
+                Some("10.1.2.0/24"),
+                Some("0.0.0.0/0"),
+                Some("::/0"),
+                Some("10.0.0.0/8"),
+                Some("10.1.0.0/16"),
+                Some("10.1.2.0/25"),
+                Some("10.1.2.128/25"),
+                Some("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"),
+                Some("10.1.2.0/24"),
+            ]),
+            nets(&[
+                Some("10.0.0.0/16"),
+                Some("10.0.0.1/24"),
+                Some("10.0.0.2/24"),
+            ]),
+            nets(&[None, None]),
+            nets(&[]),
+        ]
+    }
+
+    /// Rows other than `idx` whose network strictly contains the network of `idx`.
+    fn containing_rows(networks: &[Option<IpNetwork>], idx: usize) -> Vec<usize> {
+        let Some(network) = networks[idx] else {
+            return Vec::new();
+        };
+
+        (0..networks.len())
+            .filter(|other| {
+                networks[*other].is_some_and(|candidate| {
+                    network_sort_key(&candidate) != network_sort_key(&network)
+                        && network_contains(&candidate, &network)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parents_match_a_pairwise_scan() {
+        for networks in fixtures() {
+            let expected = (0..networks.len())
+                .map(|idx| {
+                    containing_rows(&networks, idx)
+                        .into_iter()
+                        .max_by_key(|other| {
+                            (networks[*other].unwrap().prefix(), usize::MAX - other)
+                        })
+                        .map(|other| networks[other].unwrap())
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(texts(&network_parents(&networks)), texts(&expected));
+        }
+    }
+
+    #[test]
+    fn depths_match_a_pairwise_scan() {
+        for networks in fixtures() {
+            let expected = (0..networks.len())
+                .map(|idx| {
+                    networks[idx]?;
+                    let mut keys = containing_rows(&networks, idx)
+                        .into_iter()
+                        .map(|other| network_sort_key(&networks[other].unwrap()))
+                        .collect::<Vec<_>>();
+                    keys.sort_unstable();
+                    keys.dedup();
+                    Some(keys.len())
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(network_depths(&networks), expected);
+        }
+    }
+
+    #[test]
+    fn host_bit_variants_share_the_real_parent() {
+        let networks = nets(&[
+            Some("10.0.0.0/16"),
+            Some("10.0.0.1/24"),
+            Some("10.0.0.2/24"),
+        ]);
+
+        assert_eq!(
+            texts(&network_parents(&networks)),
+            [
+                None,
+                Some("10.0.0.0/16".to_string()),
+                Some("10.0.0.0/16".to_string())
+            ]
+        );
+        assert_eq!(network_depths(&networks), [Some(0), Some(1), Some(1)]);
+
+        let networks = nets(&[Some("10.0.0.1/24"), None, Some("10.0.0.2/24")]);
+        assert_eq!(texts(&network_parents(&networks)), [None, None, None]);
+        assert_eq!(network_depths(&networks), [Some(0), None, Some(0)]);
+    }
+
+    /// The all-pairs loop `cidr_is_root` used before the sort and sweep.
+    fn all_pairs_has_parent(networks: &[Option<IpNetwork>]) -> Vec<bool> {
+        networks
+            .iter()
+            .enumerate()
+            .map(|(idx, network)| {
+                let Some(network) = network else {
+                    return false;
+                };
+                networks.iter().enumerate().any(|(other_idx, candidate)| {
+                    other_idx != idx
+                        && candidate.is_some_and(|candidate| {
+                            candidate != *network && network_contains(&candidate, network)
+                        })
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn networks_with_parent_matches_the_all_pairs_loop() {
+        for networks in fixtures() {
+            assert_eq!(
+                networks_with_parent(&networks),
+                all_pairs_has_parent(&networks)
+            );
+        }
+    }
+
+    #[test]
+    fn networks_with_parent_keeps_duplicate_and_null_semantics() {
+        let duplicates = nets(&[Some("10.0.0.0/8"), Some("10.0.0.0/8"), None]);
+        assert_eq!(networks_with_parent(&duplicates), [false, false, false]);
+
+        let host_bits = nets(&[
+            Some("10.0.0.1/24"),
+            Some("10.0.0.2/24"),
+            Some("10.0.0.1/24"),
+        ]);
+        assert_eq!(networks_with_parent(&host_bits), [true, true, true]);
+
+        let families = nets(&[Some("0.0.0.0/0"), Some("::ffff:10.0.0.0/104"), Some("::/0")]);
+        assert_eq!(networks_with_parent(&families), [false, true, false]);
+    }
+
+    #[test]
+    fn overlapping_rows_match_a_pairwise_scan() {
+        for networks in fixtures() {
+            let expected = (0..networks.len())
+                .map(|idx| {
+                    (0..networks.len())
+                        .filter(|other| {
+                            *other != idx
+                                && networks[idx]
+                                    .zip(networks[*other])
+                                    .is_some_and(|(left, right)| networks_overlap(&left, &right))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(overlapping_rows(&networks), expected);
+        }
+    }
+
+    #[test]
+    fn overlapping_rows_include_duplicates_and_host_bit_variants() {
+        let networks = nets(&[
+            Some("10.0.0.1/24"),
+            None,
+            Some("10.0.0.2/24"),
+            Some("10.0.0.1/24"),
+            Some("10.0.1.0/24"),
+            Some("::/0"),
+        ]);
+
+        assert_eq!(
+            overlapping_rows(&networks),
+            [vec![2, 3], vec![], vec![0, 3], vec![0, 2], vec![], vec![]]
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use pyo3::prelude::*;
 
 pub mod expressions;
+mod notation;
+mod ranges;
+mod trie;
 
 /// A Polars plugin for network-related computations implemented in Rust.
 #[pymodule]
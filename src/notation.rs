@@ -0,0 +1,507 @@
+use ipnetwork::{IpNetwork, IpNetworkError};
+use serde::Deserialize;
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::ranges::range_to_networks;
+
+/// Network notations accepted on top of `addr/prefix`.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Notation {
+    /// Address and netmask, such as `10.0.0.0 255.255.255.0` or `10.0.0.0/255.255.255.0`.
+    Netmask,
+    /// Address and Cisco wildcard mask, such as `10.0.0.0 0.0.0.255`.
+    Wildcard,
+    /// IPv4 address with trailing `*` octets, such as `10.0.0.*`.
+    Glob,
+    /// Inclusive address range covering exactly one network, such as `10.0.0.0-10.0.0.255`.
+    Range,
+}
+
+/// Parse `addr/prefix` text, falling back to `notations` in order when it is not CIDR.
+///
+/// Surrounding whitespace is ignored as soon as any notation is enabled. The CIDR parse error
+/// is kept when no notation matches.
+pub(crate) fn parse_network(
+    text: &str,
+    notations: &[Notation],
+) -> Result<IpNetwork, IpNetworkError> {
+    text.parse::<IpNetwork>().or_else(|err| {
+        let text = text.trim();
+        if notations.is_empty() {
+            return Err(err);
+        }
+        if let Ok(network) = text.parse::<IpNetwork>() {
+            return Ok(network);
+        }
+
+        notations
+            .iter()
+            .find_map(|notation| match notation {
+                Notation::Netmask => parse_masked(text, false),
+                Notation::Wildcard => parse_masked(text, true),
+                Notation::Glob => parse_glob(text),
+                Notation::Range => parse_range(text),
+            })
+            .ok_or(err)
+    })
+}
+
+/// Parse an address and a netmask, or a wildcard mask when `wildcard` is set, separated by
+/// whitespace or `/`.
+///
+/// `0.0.0.0` and all-ones masks are valid in both readings, one of them a host prefix. When the
+/// requested reading would leave host bits set, as in the Cisco host entry `10.0.0.5 0.0.0.0`,
+/// the host prefix is used rather than widening the entry to the whole address space.
+fn parse_masked(text: &str, wildcard: bool) -> Option<IpNetwork> {
+    let (address, mask) = text.split_once(|c: char| c == '/' || c.is_whitespace())?;
+    let address = address.trim_end().parse::<IpAddr>().ok()?;
+    let mask = mask.trim_start().parse::<IpAddr>().ok()?;
+
+    let (mask, width) = match (address, mask) {
+        (IpAddr::V4(_), IpAddr::V4(mask)) => (u128::from(u32::from(mask)), 32),
+        (IpAddr::V6(_), IpAddr::V6(mask)) => (u128::from(mask), 128),
+        _ => return None,
+    };
+
+    let (prefix, other_prefix) = if wildcard {
+        (wildcard_prefix(mask, width), mask_prefix(mask, width))
+    } else {
+        (mask_prefix(mask, width), wildcard_prefix(mask, width))
+    };
+
+    let network = IpNetwork::new(address, prefix?).ok()?;
+    match other_prefix {
+        Some(other_prefix) if network.ip() != network.network() => {
+            IpNetwork::new(address, other_prefix).ok()
+        }
+        _ => Some(network),
+    }
+}
+
+/// Prefix length of a netmask made of leading ones followed by zeros.
+fn mask_prefix(mask: u128, width: u32) -> Option<u8> {
+    let wildcard = !mask & (u128::MAX >> (128 - width));
+    wildcard_prefix(wildcard, width)
+}
+
+/// Prefix length of a wildcard mask made of leading zeros followed by ones.
+fn wildcard_prefix(wildcard: u128, width: u32) -> Option<u8> {
+    if wildcard & wildcard.wrapping_add(1) != 0 {
+        return None;
+    }
+    Some((width - wildcard.count_ones()) as u8)
+}
+
+/// Parse an IPv4 address whose trailing octets are `*`, such as `10.0.*.*`.
+fn parse_glob(text: &str) -> Option<IpNetwork> {
+    let parts = text.split('.').collect::<Vec<_>>();
+    if parts.len() != 4 {
+        return None;
+    }
+
+    let fixed = parts.iter().take_while(|part| **part != "*").count();
+    if parts[fixed..].iter().any(|part| *part != "*") {
+        return None;
+    }
+
+    let mut octets = [0_u8; 4];
+    for (octet, part) in octets.iter_mut().zip(&parts[..fixed]) {
+        *octet = part.parse().ok()?;
+    }
+
+    IpNetwork::new(IpAddr::V4(Ipv4Addr::from(octets)), (fixed * 8) as u8).ok()
+}
+
+/// Parse a `start-end` range, accepted only when it is exactly one network.
+fn parse_range(text: &str) -> Option<IpNetwork> {
+    let (start, end) = text.split_once('-')?;
+    let start = start.trim_end().parse::<IpAddr>().ok()?;
+    let end = end.trim_start().parse::<IpAddr>().ok()?;
+
+    let (range, version) = match (start, end) {
+        (IpAddr::V4(start), IpAddr::V4(end)) => (
+            (u128::from(u32::from(start)), u128::from(u32::from(end))),
+            4,
+        ),
+        (IpAddr::V6(start), IpAddr::V6(end)) => ((u128::from(start), u128::from(end)), 6),
+        _ => return None,
+    };
+    if range.0 > range.1 {
+        return None;
+    }
+
+    match range_to_networks(range, version).as_slice() {
+        [network] => Some(*network),
+        _ => None,
+    }
+}
+
+/// Human-readable reason `text` failed to parse as a network under `notations`.
+pub(crate) fn parse_error_reason(text: &str, notations: &[Notation]) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return "empty value".to_string();
+    }
+    if trimmed != text && parse_network(trimmed, notations).is_ok() {
+        return "leading or trailing whitespace".to_string();
+    }
+    let text = trimmed;
+
+    if let Some(reason) = notations
+        .iter()
+        .find_map(|notation| notation_error(text, *notation))
+    {
+        return reason;
+    }
+
+    let enabled = |accepted: fn(&Notation) -> bool| notations.iter().any(accepted);
+    if text.contains(char::is_whitespace)
+        && !enabled(|notation| matches!(notation, Notation::Netmask | Notation::Wildcard))
+    {
+        return "netmask and wildcard notations are not enabled".to_string();
+    }
+    if text.contains('-') && !enabled(|notation| matches!(notation, Notation::Range)) {
+        return "range notation is not enabled".to_string();
+    }
+    if text.contains('*') && !enabled(|notation| matches!(notation, Notation::Glob)) {
+        return "glob notation is not enabled".to_string();
+    }
+
+    let (address, prefix) =
+        match text.split_once(|c: char| c == '/' || c == '-' || c.is_whitespace()) {
+            Some((address, prefix)) => (address, Some(prefix.trim_start())),
+            None => (text, None),
+        };
+    let address = match address.parse::<IpAddr>() {
+        Ok(address) => address,
+        Err(_) => return address_error(address),
+    };
+    let (version, width) = match address {
+        IpAddr::V4(_) => (4, 32),
+        IpAddr::V6(_) => (6, 128),
+    };
+
+    match prefix.map(|prefix| (prefix, prefix.parse::<u32>())) {
+        Some((_, Ok(prefix))) if prefix > width => {
+            format!("prefix /{prefix} out of range for IPv{version} (0-{width})")
+        }
+        Some((mask, Err(_))) => match mask.parse::<IpAddr>() {
+            Ok(mask) if mask.is_ipv4() != address.is_ipv4() => "mixed address families".to_string(),
+            Ok(mask) => format!("netmask {mask} is not contiguous"),
+            Err(_) => format!("bad prefix length '{mask}'"),
+        },
+        _ => format!("invalid network '{text}'"),
+    }
+}
+
+/// Reason `text` written in `notation` is invalid, `None` when it is not in that notation.
+fn notation_error(text: &str, notation: Notation) -> Option<String> {
+    let (first, second) = match notation {
+        Notation::Netmask | Notation::Wildcard => {
+            text.split_once(|c: char| c == '/' || c.is_whitespace())?
+        }
+        Notation::Range => text.split_once('-')?,
+        Notation::Glob => {
+            return text
+                .contains('*')
+                .then(|| format!("bad glob '{text}', only trailing octets may be '*'"));
+        }
+    };
+
+    let first = first.trim_end().parse::<IpAddr>().ok()?;
+    let second = second.trim_start().parse::<IpAddr>().ok()?;
+    if first.is_ipv4() != second.is_ipv4() {
+        return Some("mixed address families".to_string());
+    }
+
+    match notation {
+        Notation::Netmask => Some(format!("netmask {second} is not contiguous")),
+        Notation::Wildcard => Some(format!("wildcard mask {second} is not contiguous")),
+        _ if first > second => Some(format!("range start {first} is after its end {second}")),
+        _ => Some(format!(
+            "range {first}-{second} is not a single network, use from_range to list its CIDRs"
+        )),
+    }
+}
+
+/// Reason an address that failed to parse is invalid, pointing at the bad octet for IPv4.
+fn address_error(address: &str) -> String {
+    if address.contains(':') {
+        return format!("bad IPv6 address '{address}'");
+    }
+
+    let octets = address.split('.').collect::<Vec<_>>();
+    if octets.len() != 4 {
+        return format!("expected 4 octets in '{address}' (got {})", octets.len());
+    }
+
+    let bad_octet = octets.iter().find(|octet| {
+        octet.parse::<u8>().is_err() || (octet.len() > 1 && octet.starts_with(['0', '+']))
+    });
+    match bad_octet {
+        Some(octet) => format!("bad octet '{octet}' in '{address}'"),
+        None => format!("bad address '{address}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Notation; 4] = [
+        Notation::Netmask,
+        Notation::Wildcard,
+        Notation::Glob,
+        Notation::Range,
+    ];
+
+    fn parsed(text: &str, notations: &[Notation]) -> Option<String> {
+        parse_network(text, notations)
+            .ok()
+            .map(|network| network.to_string())
+    }
+
+    #[test]
+    fn cidr_text_needs_no_notation() {
+        assert_eq!(parsed("10.0.0.0/24", &[]).as_deref(), Some("10.0.0.0/24"));
+        assert_eq!(parsed("0.0.0.0/0", &[]).as_deref(), Some("0.0.0.0/0"));
+        assert_eq!(parsed("::1/128", &[]).as_deref(), Some("::1/128"));
+        assert_eq!(parsed("10.0.0.0 255.255.255.0", &[]), None);
+        assert_eq!(parsed("10.0.0.*", &[]), None);
+    }
+
+    #[test]
+    fn padded_cidr_text_parses_once_a_notation_is_enabled() {
+        assert_eq!(parsed(" 10.0.0.0/8", &[]), None);
+        assert_eq!(
+            parsed(" 10.0.0.0/8\t", &[Notation::Glob]).as_deref(),
+            Some("10.0.0.0/8")
+        );
+        assert_eq!(parsed("  ::1/128 ", &ALL).as_deref(), Some("::1/128"));
+        assert_eq!(
+            parsed(" 10.0.0.0 255.0.0.0 ", &ALL).as_deref(),
+            Some("10.0.0.0/8")
+        );
+    }
+
+    #[test]
+    fn netmasks_convert_to_prefixes() {
+        let netmask = [Notation::Netmask];
+        assert_eq!(
+            parsed("10.0.0.0 255.255.255.0", &netmask).as_deref(),
+            Some("10.0.0.0/24")
+        );
+        assert_eq!(
+            parsed("10.0.0.0/255.255.255.0", &netmask).as_deref(),
+            Some("10.0.0.0/24")
+        );
+        assert_eq!(
+            parsed("10.0.0.1  255.255.255.255", &netmask).as_deref(),
+            Some("10.0.0.1/32")
+        );
+        assert_eq!(
+            parsed("0.0.0.0 0.0.0.0", &netmask).as_deref(),
+            Some("0.0.0.0/0")
+        );
+        assert_eq!(
+            parsed("2001:db8:: ffff:ffff::", &netmask).as_deref(),
+            Some("2001:db8::/32")
+        );
+        assert_eq!(
+            parsed("::1 ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff", &netmask).as_deref(),
+            Some("::1/128")
+        );
+        assert_eq!(parsed("10.0.0.0 255.0.255.0", &netmask), None);
+        assert_eq!(parsed("10.0.0.0 ffff::", &netmask), None);
+    }
+
+    #[test]
+    fn wildcard_masks_convert_to_prefixes() {
+        let wildcard = [Notation::Wildcard];
+        assert_eq!(
+            parsed("10.0.0.0 0.0.0.255", &wildcard).as_deref(),
+            Some("10.0.0.0/24")
+        );
+        assert_eq!(
+            parsed("10.0.0.1 0.0.0.0", &wildcard).as_deref(),
+            Some("10.0.0.1/32")
+        );
+        assert_eq!(
+            parsed("0.0.0.0 255.255.255.255", &wildcard).as_deref(),
+            Some("0.0.0.0/0")
+        );
+        assert_eq!(parsed("10.0.0.0 0.0.255.0", &wildcard), None);
+    }
+
+    #[test]
+    fn ambiguous_masks_never_widen_host_entries() {
+        // 0.0.0.0 and 255.255.255.255 are valid both as a netmask and as a wildcard mask.
+        let netmask_first = [Notation::Netmask, Notation::Wildcard];
+        let wildcard_first = [Notation::Wildcard, Notation::Netmask];
+
+        for notations in [&netmask_first[..], &wildcard_first, &[Notation::Netmask]] {
+            assert_eq!(
+                parsed("10.0.0.5 0.0.0.0", notations).as_deref(),
+                Some("10.0.0.5/32")
+            );
+        }
+        for notations in [&netmask_first[..], &wildcard_first, &[Notation::Wildcard]] {
+            assert_eq!(
+                parsed("10.0.0.5 255.255.255.255", notations).as_deref(),
+                Some("10.0.0.5/32")
+            );
+        }
+        assert_eq!(
+            parsed("2001:db8::1 ::", &[Notation::Netmask]).as_deref(),
+            Some("2001:db8::1/128")
+        );
+    }
+
+    #[test]
+    fn notation_order_settles_ambiguous_masks_without_host_bits() {
+        let netmask_first = [Notation::Netmask, Notation::Wildcard];
+        let wildcard_first = [Notation::Wildcard, Notation::Netmask];
+
+        assert_eq!(
+            parsed("0.0.0.0 0.0.0.0", &netmask_first).as_deref(),
+            Some("0.0.0.0/0")
+        );
+        assert_eq!(
+            parsed("0.0.0.0 0.0.0.0", &wildcard_first).as_deref(),
+            Some("0.0.0.0/32")
+        );
+        assert_eq!(
+            parsed("0.0.0.0 255.255.255.255", &netmask_first).as_deref(),
+            Some("0.0.0.0/32")
+        );
+        assert_eq!(
+            parsed("0.0.0.0 255.255.255.255", &wildcard_first).as_deref(),
+            Some("0.0.0.0/0")
+        );
+
+        // Masks valid in only one notation fall through to it whatever the order.
+        assert_eq!(
+            parsed("10.0.0.0 0.0.0.255", &netmask_first).as_deref(),
+            Some("10.0.0.0/24")
+        );
+        assert_eq!(
+            parsed("10.0.0.0 255.255.255.0", &wildcard_first).as_deref(),
+            Some("10.0.0.0/24")
+        );
+    }
+
+    #[test]
+    fn globs_cover_trailing_octets() {
+        let glob = [Notation::Glob];
+        assert_eq!(parsed("10.0.0.*", &glob).as_deref(), Some("10.0.0.0/24"));
+        assert_eq!(parsed("10.*.*.*", &glob).as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(parsed("*.*.*.*", &glob).as_deref(), Some("0.0.0.0/0"));
+        assert_eq!(parsed("10.*.0.*", &glob), None);
+        assert_eq!(parsed("10.0.*", &glob), None);
+        assert_eq!(parsed("10.0.256.*", &glob), None);
+    }
+
+    #[test]
+    fn ranges_must_form_a_single_network() {
+        let range = [Notation::Range];
+        assert_eq!(
+            parsed("10.0.0.0-10.0.0.255", &range).as_deref(),
+            Some("10.0.0.0/24")
+        );
+        assert_eq!(
+            parsed("0.0.0.0 - 255.255.255.255", &range).as_deref(),
+            Some("0.0.0.0/0")
+        );
+        assert_eq!(
+            parsed("10.0.0.7-10.0.0.7", &range).as_deref(),
+            Some("10.0.0.7/32")
+        );
+        assert_eq!(
+            parsed("::-ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff", &range).as_deref(),
+            Some("::/0")
+        );
+        assert_eq!(parsed("10.0.0.1-10.0.0.2", &range), None);
+        assert_eq!(parsed("10.0.0.255-10.0.0.0", &range), None);
+        assert_eq!(parsed("10.0.0.0-::ff", &range), None);
+    }
+
+    #[test]
+    fn failed_notations_keep_the_cidr_error() {
+        let err = parse_network("10.0.0.0/33", &ALL).unwrap_err();
+        assert_eq!(err, "10.0.0.0/33".parse::<IpNetwork>().unwrap_err());
+    }
+
+    #[test]
+    fn parse_error_reason_explains_cidr_failures() {
+        assert_eq!(parse_error_reason("  ", &[]), "empty value");
+        assert_eq!(
+            parse_error_reason("10.0.0.0/33", &[]),
+            "prefix /33 out of range for IPv4 (0-32)"
+        );
+        assert_eq!(
+            parse_error_reason("::/129", &[]),
+            "prefix /129 out of range for IPv6 (0-128)"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.256/24", &[]),
+            "bad octet '256' in '10.0.0.256'"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0/24", &[]),
+            "expected 4 octets in '10.0.0' (got 3)"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.0/x", &[]),
+            "bad prefix length 'x'"
+        );
+    }
+
+    #[test]
+    fn parse_error_reason_flags_padded_values() {
+        assert_eq!(
+            parse_error_reason(" 10.0.0.0/8", &[]),
+            "leading or trailing whitespace"
+        );
+        assert_eq!(
+            parse_error_reason("::/0\n", &[]),
+            "leading or trailing whitespace"
+        );
+        assert_eq!(
+            parse_error_reason(" 10.0.0.0/33 ", &[]),
+            "prefix /33 out of range for IPv4 (0-32)"
+        );
+    }
+
+    #[test]
+    fn parse_error_reason_explains_notation_failures() {
+        assert_eq!(
+            parse_error_reason("10.0.0.0 255.255.255.0", &[]),
+            "netmask and wildcard notations are not enabled"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.*", &[]),
+            "glob notation is not enabled"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.0 255.0.255.0", &[Notation::Netmask]),
+            "netmask 255.0.255.0 is not contiguous"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.0 ::", &[Notation::Netmask]),
+            "mixed address families"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.9-10.0.0.1", &[Notation::Range]),
+            "range start 10.0.0.9 is after its end 10.0.0.1"
+        );
+        assert_eq!(
+            parse_error_reason("10.0.0.1-10.0.0.2", &[Notation::Range]),
+            "range 10.0.0.1-10.0.0.2 is not a single network, use from_range to list its CIDRs"
+        );
+        assert_eq!(
+            parse_error_reason("10.*.0.*", &[Notation::Glob]),
+            "bad glob '10.*.0.*', only trailing octets may be '*'"
+        );
+    }
+}
@@ -0,0 +1,548 @@
+use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Inclusive address range, IPv4 values occupying the low 32 bits.
+pub(crate) type AddressRange = (u128, u128);
+
+/// First address of `::ffff:0:0/96`, which IPv4 addresses are mapped into.
+const IPV4_MAPPED_START: u128 = 0xffff << 32;
+
+/// Number of addresses, kept with a carry bit since `::/0` holds 2^128 addresses.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct AddressCount {
+    carry: bool,
+    low: u128,
+}
+
+impl AddressCount {
+    pub(crate) fn of_range(range: AddressRange) -> Self {
+        match (range.1 - range.0).checked_add(1) {
+            Some(low) => AddressCount { carry: false, low },
+            None => AddressCount {
+                carry: true,
+                low: 0,
+            },
+        }
+    }
+
+    pub(crate) fn add(self, other: AddressCount) -> Self {
+        let (low, overflow) = self.low.overflowing_add(other.low);
+        AddressCount {
+            carry: self.carry || other.carry || overflow,
+            low,
+        }
+    }
+
+    pub(crate) fn to_u64(self) -> Option<u64> {
+        if self.carry {
+            return None;
+        }
+        u64::try_from(self.low).ok()
+    }
+
+    pub(crate) fn to_f64(self) -> f64 {
+        let carry = if self.carry { 2_f64.powi(128) } else { 0.0 };
+        self.low as f64 + carry
+    }
+}
+
+impl fmt::Display for AddressCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.carry {
+            return write!(f, "{}", self.low);
+        }
+
+        // 2^128 = 10 * 34028236692093846346337460743176821145 + 6
+        let digit = self.low % 10 + 6;
+        let tens =
+            34_028_236_692_093_846_346_337_460_743_176_821_145_u128 + self.low / 10 + digit / 10;
+        write!(f, "{}{}", tens, digit % 10)
+    }
+}
+
+pub(crate) fn network_range(network: &IpNetwork) -> AddressRange {
+    match network {
+        IpNetwork::V4(net) => (
+            u128::from(u32::from(net.network())),
+            u128::from(u32::from(net.broadcast())),
+        ),
+        IpNetwork::V6(net) => {
+            let start = u128::from(net.network());
+            let host_mask = u128::MAX.checked_shr(u32::from(net.prefix())).unwrap_or(0);
+            (start, start | host_mask)
+        }
+    }
+}
+
+/// Sort ranges and merge the overlapping or adjacent ones.
+pub(crate) fn merge_ranges(mut ranges: Vec<AddressRange>) -> Vec<AddressRange> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<AddressRange> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// Minimal list of networks of the given IP version covering `start..=end` exactly.
+pub(crate) fn range_to_networks(range: AddressRange, version: u8) -> Vec<IpNetwork> {
+    let width: u32 = if version == 4 { 32 } else { 128 };
+    let (mut start, end) = range;
+    let mut networks = Vec::new();
+
+    loop {
+        let span = end - start;
+        // Largest power of two not exceeding the remaining address count.
+        let span_bits = if span == u128::MAX {
+            128
+        } else {
+            127 - (span + 1).leading_zeros()
+        };
+        let align_bits = if start == 0 {
+            width
+        } else {
+            start.trailing_zeros().min(width)
+        };
+        let bits = span_bits.min(align_bits);
+        let prefix = (width - bits) as u8;
+
+        networks.push(match version {
+            4 => IpNetwork::V4(
+                Ipv4Network::new(Ipv4Addr::from(start as u32), prefix)
+                    .expect("prefix is within the IPv4 width"),
+            ),
+            _ => IpNetwork::V6(
+                Ipv6Network::new(Ipv6Addr::from(start), prefix)
+                    .expect("prefix is within the IPv6 width"),
+            ),
+        });
+
+        let block_end = start | (u128::MAX.checked_shr(128 - bits).unwrap_or(0));
+        if block_end >= end {
+            break;
+        }
+        start = block_end + 1;
+    }
+
+    networks
+}
+
+/// Set of addresses kept as sorted, merged ranges per family.
+pub(crate) struct RangeSet {
+    ipv4: Vec<AddressRange>,
+    ipv6: Vec<AddressRange>,
+}
+
+impl RangeSet {
+    pub(crate) fn from_networks(networks: &[IpNetwork]) -> Self {
+        let (ipv4, ipv6) = split_ranges(networks);
+        RangeSet {
+            ipv4: merge_ranges(ipv4),
+            ipv6: merge_ranges(ipv6),
+        }
+    }
+
+    /// Minimal list of networks covering the set, IPv4 first.
+    pub(crate) fn to_networks(&self) -> Vec<IpNetwork> {
+        let mut networks = ranges_to_networks(&self.ipv4, 4);
+        networks.extend(ranges_to_networks(&self.ipv6, 6));
+        networks
+    }
+
+    pub(crate) fn union(&self, other: &RangeSet) -> RangeSet {
+        RangeSet {
+            ipv4: merge_ranges([self.ipv4.as_slice(), other.ipv4.as_slice()].concat()),
+            ipv6: merge_ranges([self.ipv6.as_slice(), other.ipv6.as_slice()].concat()),
+        }
+    }
+
+    pub(crate) fn intersection(&self, other: &RangeSet) -> RangeSet {
+        RangeSet {
+            ipv4: intersect_ranges(&self.ipv4, &other.ipv4),
+            ipv6: intersect_ranges(&self.ipv6, &other.ipv6),
+        }
+    }
+
+    pub(crate) fn difference(&self, other: &RangeSet) -> RangeSet {
+        let difference = |ranges: &[AddressRange], removed: &[AddressRange]| {
+            ranges
+                .iter()
+                .flat_map(|range| subtract_ranges(*range, removed))
+                .collect()
+        };
+
+        RangeSet {
+            ipv4: difference(&self.ipv4, &other.ipv4),
+            ipv6: difference(&self.ipv6, &other.ipv6),
+        }
+    }
+
+    pub(crate) fn symmetric_difference(&self, other: &RangeSet) -> RangeSet {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// Number of IPv4 and IPv6 addresses in the set, `None` for a family it holds none of.
+    pub(crate) fn family_address_counts(&self) -> (Option<AddressCount>, Option<AddressCount>) {
+        let count = |ranges: &[AddressRange]| (!ranges.is_empty()).then(|| count_ranges(ranges));
+        (count(&self.ipv4), count(&self.ipv6))
+    }
+
+    /// Number of addresses in the set once IPv4 is mapped into `::ffff:0:0/96`, so IPv4
+    /// addresses also covered by IPv6 networks of that block are counted once.
+    pub(crate) fn mapped_address_count(&self) -> AddressCount {
+        let mapped = self
+            .ipv4
+            .iter()
+            .map(|(start, end)| (IPV4_MAPPED_START | start, IPV4_MAPPED_START | end));
+        let ranges = self.ipv6.iter().copied().chain(mapped).collect();
+        count_ranges(&merge_ranges(ranges))
+    }
+
+    /// Minimal list of networks covering `network` minus the addresses of the set.
+    pub(crate) fn subtract_from(&self, network: &IpNetwork) -> Vec<IpNetwork> {
+        let (removed, version) = match network {
+            IpNetwork::V4(_) => (&self.ipv4, 4),
+            IpNetwork::V6(_) => (&self.ipv6, 6),
+        };
+
+        let remaining = subtract_ranges(network_range(network), removed);
+        ranges_to_networks(&remaining, version)
+    }
+}
+
+/// Number of addresses in disjoint ranges.
+fn count_ranges(ranges: &[AddressRange]) -> AddressCount {
+    ranges.iter().fold(AddressCount::default(), |count, range| {
+        count.add(AddressCount::of_range(*range))
+    })
+}
+
+/// Overlapping parts of two sorted, merged range lists.
+fn intersect_ranges(left: &[AddressRange], right: &[AddressRange]) -> Vec<AddressRange> {
+    let mut intersection = Vec::new();
+    let (mut left_idx, mut right_idx) = (0, 0);
+
+    while left_idx < left.len() && right_idx < right.len() {
+        let (left_start, left_end) = left[left_idx];
+        let (right_start, right_end) = right[right_idx];
+
+        let start = left_start.max(right_start);
+        let end = left_end.min(right_end);
+        if start <= end {
+            intersection.push((start, end));
+        }
+
+        if left_end < right_end {
+            left_idx += 1;
+        } else {
+            right_idx += 1;
+        }
+    }
+
+    intersection
+}
+
+/// Parts of `base` not covered by `removed`, which must be sorted and merged.
+fn subtract_ranges(base: AddressRange, removed: &[AddressRange]) -> Vec<AddressRange> {
+    let (mut start, end) = base;
+    let mut remaining = Vec::new();
+
+    let first = removed.partition_point(|(_, removed_end)| *removed_end < start);
+    for &(removed_start, removed_end) in &removed[first..] {
+        if removed_start > end {
+            break;
+        }
+        if removed_start > start {
+            remaining.push((start, removed_start - 1));
+        }
+        if removed_end >= end {
+            return remaining;
+        }
+        start = removed_end + 1;
+    }
+
+    remaining.push((start, end));
+    remaining
+}
+
+/// Address ranges of the IPv4 and IPv6 networks respectively.
+fn split_ranges(networks: &[IpNetwork]) -> (Vec<AddressRange>, Vec<AddressRange>) {
+    let mut ipv4 = Vec::new();
+    let mut ipv6 = Vec::new();
+
+    for network in networks {
+        match network {
+            IpNetwork::V4(_) => ipv4.push(network_range(network)),
+            IpNetwork::V6(_) => ipv6.push(network_range(network)),
+        }
+    }
+
+    (ipv4, ipv6)
+}
+
+fn ranges_to_networks(ranges: &[AddressRange], version: u8) -> Vec<IpNetwork> {
+    ranges
+        .iter()
+        .flat_map(|range| range_to_networks(*range, version))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_POW_128: &str = "340282366920938463463374607431768211456";
+
+    fn net(text: &str) -> IpNetwork {
+        text.parse().unwrap()
+    }
+
+    fn texts(networks: &[IpNetwork]) -> Vec<String> {
+        networks.iter().map(ToString::to_string).collect()
+    }
+
+    fn set(networks: &[&str]) -> RangeSet {
+        let networks = networks.iter().map(|text| net(text)).collect::<Vec<_>>();
+        RangeSet::from_networks(&networks)
+    }
+
+    #[test]
+    fn network_range_covers_whole_and_single_address_networks() {
+        assert_eq!(network_range(&net("0.0.0.0/0")), (0, u128::from(u32::MAX)));
+        assert_eq!(
+            network_range(&net("10.0.0.1/32")),
+            (0x0a00_0001, 0x0a00_0001)
+        );
+        assert_eq!(
+            network_range(&net("10.1.2.3/8")),
+            (0x0a00_0000, 0x0aff_ffff)
+        );
+        assert_eq!(network_range(&net("::/0")), (0, u128::MAX));
+        assert_eq!(network_range(&net("::1/128")), (1, 1));
+    }
+
+    #[test]
+    fn merge_ranges_joins_overlapping_and_adjacent_ranges() {
+        let merged = merge_ranges(vec![(10, 20), (0, 4), (5, 9), (15, 30), (40, 50)]);
+        assert_eq!(merged, vec![(0, 30), (40, 50)]);
+
+        let merged = merge_ranges(vec![(u128::MAX, u128::MAX), (0, u128::MAX - 1), (3, 3)]);
+        assert_eq!(merged, vec![(0, u128::MAX)]);
+    }
+
+    #[test]
+    fn range_to_networks_covers_full_address_spaces() {
+        assert_eq!(
+            texts(&range_to_networks((0, u128::from(u32::MAX)), 4)),
+            ["0.0.0.0/0"]
+        );
+        assert_eq!(texts(&range_to_networks((0, u128::MAX), 6)), ["::/0"]);
+    }
+
+    #[test]
+    fn range_to_networks_handles_single_addresses() {
+        assert_eq!(
+            texts(&range_to_networks(
+                (u128::from(u32::MAX), u128::from(u32::MAX)),
+                4
+            )),
+            ["255.255.255.255/32"]
+        );
+        assert_eq!(
+            texts(&range_to_networks((u128::MAX, u128::MAX), 6)),
+            ["ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"]
+        );
+        assert_eq!(texts(&range_to_networks((0, 0), 6)), ["::/128"]);
+    }
+
+    #[test]
+    fn range_to_networks_aligns_unaligned_bounds() {
+        assert_eq!(
+            texts(&range_to_networks((0x0a00_0001, 0x0a00_0006), 4)),
+            ["10.0.0.1/32", "10.0.0.2/31", "10.0.0.4/31", "10.0.0.6/32"]
+        );
+        assert_eq!(
+            texts(&range_to_networks((0x0a00_0000, 0x0a00_01ff), 4)),
+            ["10.0.0.0/23"]
+        );
+
+        let ipv4 = range_to_networks((1, u128::from(u32::MAX)), 4);
+        assert_eq!(ipv4.len(), 32);
+        assert_eq!(ipv4[0].to_string(), "0.0.0.1/32");
+        assert_eq!(ipv4[31].to_string(), "128.0.0.0/1");
+
+        let ipv6 = range_to_networks((1, u128::MAX), 6);
+        assert_eq!(ipv6.len(), 128);
+        assert_eq!(ipv6[0].to_string(), "::1/128");
+        assert_eq!(ipv6[127].to_string(), "8000::/1");
+
+        let ipv6 = range_to_networks((0, u128::MAX - 1), 6);
+        assert_eq!(ipv6.len(), 128);
+        assert_eq!(ipv6[0].to_string(), "::/1");
+        assert_eq!(
+            ipv6[127].to_string(),
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/128"
+        );
+    }
+
+    #[test]
+    fn range_sets_collapse_duplicates_and_full_spaces() {
+        let full = set(&[
+            "0.0.0.0/1",
+            "128.0.0.0/1",
+            "10.0.0.0/8",
+            "10.0.0.0/8",
+            "::/0",
+        ]);
+        assert_eq!(texts(&full.to_networks()), ["0.0.0.0/0", "::/0"]);
+    }
+
+    #[test]
+    fn subtract_ranges_handles_the_address_space_bounds() {
+        assert_eq!(
+            subtract_ranges((0, u128::MAX), &[(0, 0)]),
+            vec![(1, u128::MAX)]
+        );
+        assert_eq!(
+            subtract_ranges((0, u128::MAX), &[(u128::MAX, u128::MAX)]),
+            vec![(0, u128::MAX - 1)]
+        );
+        assert_eq!(subtract_ranges((0, u128::MAX), &[(0, u128::MAX)]), vec![]);
+        assert_eq!(
+            subtract_ranges((0, u128::MAX), &[(5, 9), (20, 29)]),
+            vec![(0, 4), (10, 19), (30, u128::MAX)]
+        );
+    }
+
+    #[test]
+    fn subtract_ranges_ignores_ranges_outside_the_base() {
+        assert_eq!(
+            subtract_ranges((10, 20), &[(0, 9), (21, 30)]),
+            vec![(10, 20)]
+        );
+        assert_eq!(
+            subtract_ranges((10, 20), &[(0, 10), (20, 30)]),
+            vec![(11, 19)]
+        );
+        assert_eq!(subtract_ranges((10, 20), &[]), vec![(10, 20)]);
+    }
+
+    #[test]
+    fn subtract_from_removes_partial_overlaps() {
+        let removed = set(&["10.0.0.0/26", "10.0.0.192/27", "192.168.0.0/16"]);
+        assert_eq!(
+            texts(&removed.subtract_from(&net("10.0.0.0/24"))),
+            ["10.0.0.64/26", "10.0.0.128/26", "10.0.0.224/27"]
+        );
+        assert_eq!(
+            texts(&removed.subtract_from(&net("10.0.0.0/26"))),
+            Vec::<String>::new()
+        );
+        assert_eq!(texts(&removed.subtract_from(&net("::/0"))), ["::/0"]);
+    }
+
+    #[test]
+    fn range_sets_combine_per_family() {
+        let left = set(&["10.0.0.0/24", "2001:db8::/32"]);
+        let right = set(&["10.0.0.128/25", "10.0.1.0/24"]);
+
+        assert_eq!(
+            texts(&left.union(&right).to_networks()),
+            ["10.0.0.0/23", "2001:db8::/32"]
+        );
+        assert_eq!(
+            texts(&left.intersection(&right).to_networks()),
+            ["10.0.0.128/25"]
+        );
+        assert_eq!(
+            texts(&left.difference(&right).to_networks()),
+            ["10.0.0.0/25", "2001:db8::/32"]
+        );
+        assert_eq!(
+            texts(&left.symmetric_difference(&right).to_networks()),
+            ["10.0.0.0/25", "10.0.1.0/24", "2001:db8::/32"]
+        );
+    }
+
+    #[test]
+    fn address_count_displays_counts_beyond_u128() {
+        let full = AddressCount::of_range((0, u128::MAX));
+        assert_eq!(full.to_string(), TWO_POW_128);
+        assert_eq!(full.to_u64(), None);
+        assert_eq!(full.to_f64(), 2_f64.powi(128));
+
+        let nine_more = full.add(AddressCount::of_range((1, 9)));
+        assert_eq!(
+            nine_more.to_string(),
+            "340282366920938463463374607431768211465"
+        );
+
+        let ten_more = full.add(AddressCount::of_range((1, 10)));
+        assert_eq!(
+            ten_more.to_string(),
+            "340282366920938463463374607431768211466"
+        );
+
+        let wrapped =
+            AddressCount::of_range((0, u128::MAX - 1)).add(AddressCount::of_range((5, 5)));
+        assert_eq!(
+            wrapped.to_string(),
+            "340282366920938463463374607431768211456"
+        );
+    }
+
+    #[test]
+    fn address_count_fits_u64_up_to_its_maximum() {
+        assert_eq!(AddressCount::of_range((7, 7)).to_u64(), Some(1));
+        assert_eq!(
+            AddressCount::of_range((0, u128::from(u64::MAX) - 1)).to_u64(),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            AddressCount::of_range((0, u128::from(u64::MAX))).to_u64(),
+            None
+        );
+        assert_eq!(AddressCount::default().to_string(), "0");
+    }
+
+    #[test]
+    fn family_address_counts_skip_missing_families() {
+        let (v4, v6) = set(&["10.0.0.0/24", "10.0.0.128/25"]).family_address_counts();
+        assert_eq!(v4.and_then(AddressCount::to_u64), Some(256));
+        assert!(v6.is_none());
+
+        let (v4, v6) = set(&[]).family_address_counts();
+        assert!(v4.is_none() && v6.is_none());
+    }
+
+    #[test]
+    fn mapped_address_count_merges_ipv4_into_mapped_ipv6() {
+        let overlapping = set(&["10.0.0.0/24", "::ffff:10.0.0.0/120"]);
+        assert_eq!(overlapping.mapped_address_count().to_u64(), Some(256));
+
+        let partial = set(&["10.0.0.0/24", "::ffff:10.0.0.128/121", "::1/128"]);
+        assert_eq!(partial.mapped_address_count().to_u64(), Some(257));
+
+        let full = set(&["::/0", "10.0.0.0/8", "0.0.0.0/0"]);
+        assert_eq!(full.mapped_address_count().to_string(), TWO_POW_128);
+    }
+
+    #[test]
+    fn family_address_counts_cover_full_spaces() {
+        let full = set(&["0.0.0.0/1", "128.0.0.0/1", "::/0", "::/1"]);
+        let (v4, v6) = full.family_address_counts();
+        assert_eq!(v4.and_then(AddressCount::to_u64), Some(1 << 32));
+        assert_eq!(
+            v6.map(|count| count.to_string()).as_deref(),
+            Some(TWO_POW_128)
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use ipnetwork::IpNetwork;
+
+const NO_CHILD: u32 = 0;
+
+/// Binary prefix trie indexing a set of networks for containment queries.
+///
+/// IPv4 and IPv6 networks live in separate tries; each inserted network carries the
+/// index it was inserted with, the first insertion winning on duplicates.
+pub(crate) struct PrefixTrie {
+    ipv4: FamilyTrie,
+    ipv6: FamilyTrie,
+}
+
+impl PrefixTrie {
+    pub(crate) fn new() -> Self {
+        PrefixTrie {
+            ipv4: FamilyTrie::new(),
+            ipv6: FamilyTrie::new(),
+        }
+    }
+
+    pub(crate) fn from_networks<'a>(networks: impl IntoIterator<Item = &'a IpNetwork>) -> Self {
+        let mut trie = PrefixTrie::new();
+        for (idx, network) in networks.into_iter().enumerate() {
+            trie.insert(network, idx);
+        }
+        trie
+    }
+
+    pub(crate) fn insert(&mut self, network: &IpNetwork, value: usize) {
+        self.family_mut(network)
+            .insert(network_key(network), network.prefix(), value);
+    }
+
+    /// Index of the most specific inserted network containing `network`.
+    pub(crate) fn longest_match(&self, network: &IpNetwork) -> Option<usize> {
+        self.family(network)
+            .longest_match(network_key(network), network.prefix())
+    }
+
+    /// Whether any inserted network contains `network`.
+    pub(crate) fn contains_supernet_of(&self, network: &IpNetwork) -> bool {
+        self.longest_match(network).is_some()
+    }
+
+    /// Whether any inserted network is contained in `network`.
+    pub(crate) fn contains_subnet_of(&self, network: &IpNetwork) -> bool {
+        self.family(network)
+            .has_descendant(network_key(network), network.prefix())
+    }
+
+    fn family(&self, network: &IpNetwork) -> &FamilyTrie {
+        match network {
+            IpNetwork::V4(_) => &self.ipv4,
+            IpNetwork::V6(_) => &self.ipv6,
+        }
+    }
+
+    fn family_mut(&mut self, network: &IpNetwork) -> &mut FamilyTrie {
+        match network {
+            IpNetwork::V4(_) => &mut self.ipv4,
+            IpNetwork::V6(_) => &mut self.ipv6,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    children: [u32; 2],
+    value: Option<usize>,
+}
+
+struct FamilyTrie {
+    nodes: Vec<Node>,
+    len: usize,
+}
+
+impl FamilyTrie {
+    fn new() -> Self {
+        FamilyTrie {
+            nodes: vec![Node::default()],
+            len: 0,
+        }
+    }
+
+    fn insert(&mut self, key: u128, prefix: u8, value: usize) {
+        let mut node = 0;
+        for depth in 0..prefix {
+            let bit = key_bit(key, depth);
+            let child = self.nodes[node].children[bit];
+            node = if child == NO_CHILD {
+                let next = self.nodes.len();
+                self.nodes.push(Node::default());
+                self.nodes[node].children[bit] = next as u32;
+                next
+            } else {
+                child as usize
+            };
+        }
+
+        self.nodes[node].value.get_or_insert(value);
+        self.len += 1;
+    }
+
+    fn longest_match(&self, key: u128, prefix: u8) -> Option<usize> {
+        let mut node = 0;
+        let mut best = self.nodes[node].value;
+        for depth in 0..prefix {
+            let child = self.nodes[node].children[key_bit(key, depth)];
+            if child == NO_CHILD {
+                break;
+            }
+
+            node = child as usize;
+            if let Some(value) = self.nodes[node].value {
+                best = Some(value);
+            }
+        }
+
+        best
+    }
+
+    fn has_descendant(&self, key: u128, prefix: u8) -> bool {
+        if self.len == 0 {
+            return false;
+        }
+
+        // Nodes only exist on the path of an inserted network, so reaching the node
+        // at `prefix` means at least one inserted network lies below it.
+        let mut node = 0;
+        for depth in 0..prefix {
+            let child = self.nodes[node].children[key_bit(key, depth)];
+            if child == NO_CHILD {
+                return false;
+            }
+            node = child as usize;
+        }
+
+        true
+    }
+}
+
+/// Network address left-aligned in 128 bits so both families share the bit walk.
+fn network_key(network: &IpNetwork) -> u128 {
+    match network {
+        IpNetwork::V4(net) => u128::from(u32::from(net.network())) << 96,
+        IpNetwork::V6(net) => u128::from(net.network()),
+    }
+}
+
+fn key_bit(key: u128, depth: u8) -> usize {
+    ((key >> (127 - u32::from(depth))) & 1) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn net(text: &str) -> IpNetwork {
+        text.parse().unwrap()
+    }
+
+    fn trie(networks: &[&str]) -> PrefixTrie {
+        let networks = networks.iter().map(|text| net(text)).collect::<Vec<_>>();
+        PrefixTrie::from_networks(&networks)
+    }
+
+    #[test]
+    fn longest_match_prefers_the_most_specific_prefix() {
+        let trie = trie(&["0.0.0.0/0", "10.0.0.0/8", "10.1.0.0/16", "10.1.2.0/24"]);
+
+        assert_eq!(trie.longest_match(&net("10.1.2.3/32")), Some(3));
+        assert_eq!(trie.longest_match(&net("10.1.3.0/24")), Some(2));
+        assert_eq!(trie.longest_match(&net("10.2.0.0/16")), Some(1));
+        assert_eq!(trie.longest_match(&net("192.168.0.1/32")), Some(0));
+        assert_eq!(trie.longest_match(&net("0.0.0.0/0")), Some(0));
+    }
+
+    #[test]
+    fn longest_match_ignores_more_specific_prefixes() {
+        let trie = trie(&["10.1.2.0/24"]);
+
+        assert_eq!(trie.longest_match(&net("10.1.0.0/16")), None);
+        assert_eq!(trie.longest_match(&net("10.1.2.0/24")), Some(0));
+    }
+
+    #[test]
+    fn host_prefixes_match_only_themselves() {
+        let trie = trie(&["10.0.0.1/32", "2001:db8::1/128"]);
+
+        assert_eq!(trie.longest_match(&net("10.0.0.1/32")), Some(0));
+        assert_eq!(trie.longest_match(&net("10.0.0.2/32")), None);
+        assert_eq!(trie.longest_match(&net("2001:db8::1/128")), Some(1));
+        assert_eq!(trie.longest_match(&net("2001:db8::2/128")), None);
+        assert_eq!(
+            trie.longest_match(&net("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128")),
+            None
+        );
+    }
+
+    #[test]
+    fn families_are_kept_apart() {
+        let ipv4 = trie(&["0.0.0.0/0"]);
+        assert_eq!(ipv4.longest_match(&net("::/0")), None);
+        assert_eq!(ipv4.longest_match(&net("::1/128")), None);
+
+        let ipv6 = trie(&["::/0"]);
+        assert_eq!(
+            ipv6.longest_match(&net("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128")),
+            Some(0)
+        );
+        assert_eq!(ipv6.longest_match(&net("10.0.0.0/8")), None);
+    }
+
+    #[test]
+    fn duplicate_inserts_keep_the_first_index() {
+        let trie = trie(&["10.0.0.0/8", "10.0.0.0/8", "0.0.0.0/0", "0.0.0.0/0"]);
+
+        assert_eq!(trie.longest_match(&net("10.0.0.0/24")), Some(0));
+        assert_eq!(trie.longest_match(&net("11.0.0.0/24")), Some(2));
+    }
+
+    #[test]
+    fn empty_trie_matches_nothing() {
+        let trie = PrefixTrie::new();
+
+        assert_eq!(trie.longest_match(&net("0.0.0.0/0")), None);
+        assert_eq!(trie.longest_match(&net("::/0")), None);
+    }
+
+    #[test]
+    fn has_descendant_finds_networks_below_the_query() {
+        let trie = trie(&["10.0.0.0/8", "10.1.2.0/24", "2001:db8::1/128"]);
+
+        assert!(trie.contains_subnet_of(&net("0.0.0.0/0")));
+        assert!(trie.contains_subnet_of(&net("10.0.0.0/8")));
+        assert!(trie.contains_subnet_of(&net("10.1.0.0/16")));
+        assert!(trie.contains_subnet_of(&net("10.1.2.0/24")));
+        assert!(!trie.contains_subnet_of(&net("10.2.0.0/16")));
+        assert!(!trie.contains_subnet_of(&net("10.1.2.1/32")));
+        assert!(trie.contains_subnet_of(&net("::/0")));
+        assert!(trie.contains_subnet_of(&net("2001:db8::1/128")));
+        assert!(!trie.contains_subnet_of(&net("2001:db8::2/128")));
+    }
+
+    #[test]
+    fn subnet_and_supernet_queries_stay_within_a_family() {
+        let ipv4 = trie(&["0.0.0.0/0"]);
+        assert!(ipv4.contains_supernet_of(&net("10.0.0.0/8")));
+        assert!(!ipv4.contains_supernet_of(&net("::1/128")));
+        assert!(!ipv4.contains_subnet_of(&net("::/0")));
+
+        let empty = PrefixTrie::new();
+        assert!(!empty.contains_supernet_of(&net("0.0.0.0/0")));
+        assert!(!empty.contains_subnet_of(&net("0.0.0.0/0")));
+        assert!(!empty.contains_subnet_of(&net("::/0")));
+    }
+}